@@ -0,0 +1,87 @@
+// version_1(정적 디스패치) vs version_2(dyn) 순회 비용 비교
+// cargo bench --bench js_map_iteration
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+use trait_practice::js_map::{version_1, version_2};
+
+const SIZES: [isize; 2] = [1_000, 100_000];
+
+fn sum_v1<'a>(map: &'a impl version_1::JsMap<'a, Key = isize, Value = i64>) -> i64 {
+    map.entries().map(|(k, v)| *k as i64 ^ v).sum()
+}
+
+// 호출마다 Box<dyn Iterator> 하나 + 원소마다 가상 호출
+fn sum_v2_entries(map: &dyn version_2::JsMap<isize, i64>) -> i64 {
+    map.entries().map(|(k, v)| *k as i64 ^ v).sum()
+}
+
+// 할당 없음. 원소마다 클로저 가상 호출만 남는다
+fn sum_v2_for_each(map: &dyn version_2::JsMap<isize, i64>) -> i64 {
+    let mut sum = 0;
+    map.for_each_entry(&mut |k, v| sum += *k as i64 ^ v);
+    sum
+}
+
+// 앞쪽 몇 개만 보고 멈추는 경우. entries 는 할당 비용이 그대로 드러난다
+fn first_v2_entries(map: &dyn version_2::JsMap<isize, i64>) -> i64 {
+    map.entries().take(8).map(|(_, v)| *v).sum()
+}
+
+fn first_v2_for_each(map: &dyn version_2::JsMap<isize, i64>) -> i64 {
+    let (mut sum, mut left) = (0, 8);
+    let _ = map.try_for_each_entry(&mut |_, v| {
+        sum += v;
+        left -= 1;
+        if left == 0 {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    sum
+}
+
+fn bench_map<M>(c: &mut Criterion, name: &str, build: impl Fn(isize) -> M)
+where
+    M: for<'a> version_1::JsMap<'a, Key = isize, Value = i64> + version_2::JsMap<isize, i64>,
+{
+    let mut group = c.benchmark_group(format!("{name}/full"));
+    for size in SIZES {
+        let map = build(size);
+        group.bench_with_input(BenchmarkId::new("v1_static", size), &map, |b, map| {
+            b.iter(|| sum_v1(black_box(map)))
+        });
+        group.bench_with_input(BenchmarkId::new("v2_entries", size), &map, |b, map| {
+            b.iter(|| sum_v2_entries(black_box(map)))
+        });
+        group.bench_with_input(BenchmarkId::new("v2_for_each", size), &map, |b, map| {
+            b.iter(|| sum_v2_for_each(black_box(map)))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group(format!("{name}/first_8"));
+    let map = build(*SIZES.last().unwrap());
+    group.bench_function("v2_entries", |b| {
+        b.iter(|| first_v2_entries(black_box(&map)))
+    });
+    group.bench_function("v2_for_each", |b| {
+        b.iter(|| first_v2_for_each(black_box(&map)))
+    });
+    group.finish();
+}
+
+fn iteration(c: &mut Criterion) {
+    bench_map(c, "Vec<(isize, i64)>", |size| {
+        (0..size).map(|k| (k, k as i64 * 3)).collect::<Vec<_>>()
+    });
+    bench_map(c, "HashMap<isize, i64>", |size| {
+        (0..size)
+            .map(|k| (k, k as i64 * 3))
+            .collect::<HashMap<_, _>>()
+    });
+}
+
+criterion_group!(benches, iteration);
+criterion_main!(benches);
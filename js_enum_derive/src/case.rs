@@ -0,0 +1,122 @@
+// `#[js(rename_all = "...")]`에서 쓰는 케이스 변환. 입력은 variant 이름(PascalCase).
+#[derive(Clone, Copy)]
+pub enum RenameRule {
+    Camel,
+    Snake,
+    ScreamingSnake,
+}
+
+impl RenameRule {
+    pub fn parse(rule: &str) -> Option<Self> {
+        match rule {
+            "camelCase" => Some(RenameRule::Camel),
+            "snake_case" => Some(RenameRule::Snake),
+            "SCREAMING_SNAKE_CASE" | "SCREAMING" => Some(RenameRule::ScreamingSnake),
+            _ => None,
+        }
+    }
+
+    pub fn apply(self, variant: &str) -> String {
+        let words = split_words(variant);
+        match self {
+            RenameRule::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(i, w)| match i {
+                    0 => w.to_lowercase(),
+                    _ => capitalize(w),
+                })
+                .collect(),
+            RenameRule::Snake => words
+                .iter()
+                .map(|w| w.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            RenameRule::ScreamingSnake => words
+                .iter()
+                .map(|w| w.to_uppercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+        }
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+// "DdongJengE" -> ["Ddong", "Jeng", "E"], "HTTPServer" -> ["HTTP", "Server"]
+fn split_words(ident: &str) -> Vec<String> {
+    let chars: Vec<char> = ident.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if c.is_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if !prev.is_uppercase() || next_is_lower {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_pascal_case_and_acronyms() {
+        assert_eq!(split_words("DdongJengE"), ["Ddong", "Jeng", "E"]);
+        assert_eq!(split_words("HTTPServer"), ["HTTP", "Server"]);
+        assert_eq!(split_words("Asimo"), ["Asimo"]);
+        assert_eq!(split_words("Item2Go"), ["Item2", "Go"]);
+        assert_eq!(split_words("Snake_Case"), ["Snake", "Case"]);
+        assert!(split_words("").is_empty());
+    }
+
+    #[test]
+    fn applies_rules() {
+        let cases = [
+            ("DdongJengE", "ddongJengE", "ddong_jeng_e", "DDONG_JENG_E"),
+            ("HTTPServer", "httpServer", "http_server", "HTTP_SERVER"),
+            ("Asimo", "asimo", "asimo", "ASIMO"),
+        ];
+        for (variant, camel, snake, screaming) in cases {
+            assert_eq!(RenameRule::Camel.apply(variant), camel);
+            assert_eq!(RenameRule::Snake.apply(variant), snake);
+            assert_eq!(RenameRule::ScreamingSnake.apply(variant), screaming);
+        }
+    }
+
+    #[test]
+    fn parses_rule_names() {
+        assert!(matches!(
+            RenameRule::parse("camelCase"),
+            Some(RenameRule::Camel)
+        ));
+        assert!(matches!(
+            RenameRule::parse("SCREAMING"),
+            Some(RenameRule::ScreamingSnake)
+        ));
+        assert!(RenameRule::parse("kebab-case").is_none());
+    }
+}
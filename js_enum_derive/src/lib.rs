@@ -0,0 +1,256 @@
+// main.rs 에서 손으로 쓰던 `impl TryFrom<&str> for Enum` 을 derive 로 만든 것.
+//
+// #[derive(JsEnum)]
+// #[js(rename_all = "snake_case", case_insensitive)]
+// enum Enum {
+//     Asimo,
+//     #[js(rename = "ddongJengE")]
+//     DdongJengE,
+// }
+//
+// 생성되는 것: `TryFrom<&str>`, `FromStr`, `Display`, `Enum::ALL`, `Enum::NAMES`,
+// 그리고 잘못된 입력과 가능한 값 목록을 담는 `EnumParseError`.
+mod case;
+
+use case::RenameRule;
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, Ident, LitStr};
+
+#[proc_macro_derive(JsEnum, attributes(js))]
+pub fn derive_js_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct ContainerAttrs {
+    rename_all: Option<RenameRule>,
+    case_insensitive: bool,
+}
+
+struct Variant {
+    ident: Ident,
+    name: String,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "JsEnum can only be derived for enums",
+            ))
+        }
+    };
+    let container = parse_container_attrs(&input.attrs)?;
+
+    let mut variants = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "JsEnum only supports unit variants",
+            ));
+        }
+        let name = match parse_variant_rename(&variant.attrs)? {
+            Some(rename) => rename,
+            None => match container.rename_all {
+                Some(rule) => rule.apply(&variant.ident.to_string()),
+                None => variant.ident.to_string(),
+            },
+        };
+        if let Some(dup) = variants
+            .iter()
+            .find(|v: &&Variant| match container.case_insensitive {
+                true => v.name.eq_ignore_ascii_case(&name),
+                false => v.name == name,
+            })
+        {
+            return Err(Error::new_spanned(
+                variant,
+                format!("JsEnum name \"{name}\" is already used by `{}`", dup.ident),
+            ));
+        }
+        variants.push(Variant {
+            ident: variant.ident.clone(),
+            name,
+        });
+    }
+
+    let enum_ident = &input.ident;
+    let vis = &input.vis;
+    let error_ident = format_ident!("{}ParseError", enum_ident);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let idents: Vec<_> = variants.iter().map(|v| &v.ident).collect();
+    let names: Vec<_> = variants.iter().map(|v| &v.name).collect();
+    let matcher = match container.case_insensitive {
+        true => quote! {
+            #(
+                if value.eq_ignore_ascii_case(#names) {
+                    return Ok(#enum_ident::#idents);
+                }
+            )*
+        },
+        false => quote! {
+            match value {
+                #( #names => return Ok(#enum_ident::#idents), )*
+                _ => {}
+            }
+        },
+    };
+    let error_doc = format!("`{enum_ident}` 로 변환할 수 없는 문자열");
+
+    Ok(quote! {
+        #[doc = #error_doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis struct #error_ident {
+            pub input: String,
+            pub expected: &'static [&'static str],
+        }
+
+        impl ::std::fmt::Display for #error_ident {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                write!(
+                    f,
+                    "invalid {} value {:?}, expected one of: {}",
+                    stringify!(#enum_ident),
+                    self.input,
+                    self.expected.join(", ")
+                )
+            }
+        }
+
+        impl ::std::error::Error for #error_ident {}
+
+        impl #impl_generics #enum_ident #ty_generics #where_clause {
+            pub const ALL: &'static [Self] = &[#( #enum_ident::#idents ),*];
+            pub const NAMES: &'static [&'static str] = &[#( #names ),*];
+
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    #( #enum_ident::#idents => #names, )*
+                }
+            }
+        }
+
+        impl #impl_generics ::std::convert::TryFrom<&str> for #enum_ident #ty_generics #where_clause {
+            type Error = #error_ident;
+            fn try_from(value: &str) -> ::std::result::Result<Self, Self::Error> {
+                #matcher
+                Err(#error_ident {
+                    input: value.to_owned(),
+                    expected: Self::NAMES,
+                })
+            }
+        }
+
+        impl #impl_generics ::std::str::FromStr for #enum_ident #ty_generics #where_clause {
+            type Err = #error_ident;
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                Self::try_from(s)
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Display for #enum_ident #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    })
+}
+
+fn parse_container_attrs(attrs: &[Attribute]) -> syn::Result<ContainerAttrs> {
+    let mut container = ContainerAttrs::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("js")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename_all") {
+                let rule: LitStr = meta.value()?.parse()?;
+                container.rename_all = Some(RenameRule::parse(&rule.value()).ok_or_else(|| {
+                    Error::new_spanned(
+                        &rule,
+                        "expected \"camelCase\", \"snake_case\" or \"SCREAMING_SNAKE_CASE\"",
+                    )
+                })?);
+                Ok(())
+            } else if meta.path.is_ident("case_insensitive") {
+                container.case_insensitive = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown js attribute, expected `rename_all` or `case_insensitive`"))
+            }
+        })?;
+    }
+    Ok(container)
+}
+
+fn parse_variant_rename(attrs: &[Attribute]) -> syn::Result<Option<String>> {
+    let mut rename = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("js")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let name: LitStr = meta.value()?.parse()?;
+                rename = Some(name.value());
+                Ok(())
+            } else {
+                Err(meta.error("unknown js attribute, expected `rename`"))
+            }
+        })?;
+    }
+    Ok(rename)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn error(input: DeriveInput) -> String {
+        expand(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn duplicate_names_are_rejected() {
+        let message = error(parse_quote! {
+            enum Enum {
+                Asimo,
+                #[js(rename = "Asimo")]
+                Other,
+            }
+        });
+        assert_eq!(message, "JsEnum name \"Asimo\" is already used by `Asimo`");
+
+        // case_insensitive 이면 대소문자만 다른 이름도 겹친다
+        let message = error(parse_quote! {
+            #[js(case_insensitive)]
+            enum Enum {
+                Asimo,
+                #[js(rename = "ASIMO")]
+                Other,
+            }
+        });
+        assert_eq!(message, "JsEnum name \"ASIMO\" is already used by `Asimo`");
+    }
+
+    #[test]
+    fn bad_attributes_are_rejected() {
+        let message = error(parse_quote! {
+            #[js(rename_all = "kebab-case")]
+            enum Enum { Asimo }
+        });
+        assert!(message.starts_with("expected \"camelCase\""), "{message}");
+        let message = error(parse_quote! {
+            enum Enum { Asimo(u8) }
+        });
+        assert_eq!(message, "JsEnum only supports unit variants");
+        let message = error(parse_quote! {
+            struct Struct;
+        });
+        assert_eq!(message, "JsEnum can only be derived for enums");
+    }
+}
@@ -0,0 +1,82 @@
+use js_enum_derive::JsEnum;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsEnum)]
+enum Plain {
+    Asimo,
+    #[js(rename = "ddong")]
+    DdongJengE,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsEnum)]
+#[js(rename_all = "camelCase")]
+enum Camel {
+    DdongJengE,
+    HTTPServer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsEnum)]
+#[js(rename_all = "snake_case")]
+enum Snake {
+    DdongJengE,
+    #[js(rename = "kept")]
+    Renamed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsEnum)]
+#[js(rename_all = "SCREAMING_SNAKE_CASE", case_insensitive)]
+enum Screaming {
+    DdongJengE,
+    Asimo,
+}
+
+#[test]
+fn rename_overrides_variant_name() {
+    assert_eq!(Plain::NAMES, ["Asimo", "ddong"]);
+    assert_eq!(Plain::try_from("ddong"), Ok(Plain::DdongJengE));
+    assert!(Plain::try_from("DdongJengE").is_err());
+    assert_eq!(Plain::DdongJengE.to_string(), "ddong");
+    assert_eq!(Plain::ALL, [Plain::Asimo, Plain::DdongJengE]);
+}
+
+#[test]
+fn rename_all_rules() {
+    assert_eq!(Camel::NAMES, ["ddongJengE", "httpServer"]);
+    assert_eq!(Camel::from_str("httpServer"), Ok(Camel::HTTPServer));
+
+    // rename 이 rename_all 보다 우선한다
+    assert_eq!(Snake::NAMES, ["ddong_jeng_e", "kept"]);
+    assert_eq!(Snake::DdongJengE.as_str(), "ddong_jeng_e");
+
+    assert_eq!(Screaming::NAMES, ["DDONG_JENG_E", "ASIMO"]);
+    assert_eq!(Screaming::Asimo.to_string(), "ASIMO");
+}
+
+#[test]
+fn case_insensitive_matching() {
+    assert_eq!(
+        Screaming::try_from("ddong_jeng_e"),
+        Ok(Screaming::DdongJengE)
+    );
+    assert_eq!(Screaming::try_from("Asimo"), Ok(Screaming::Asimo));
+    // 기본은 대소문자를 구분한다
+    assert!(Camel::try_from("HTTPSERVER").is_err());
+}
+
+#[test]
+fn parse_error_lists_expected_names() {
+    let err = Camel::try_from("nope").unwrap_err();
+    assert_eq!(
+        err,
+        CamelParseError {
+            input: "nope".to_owned(),
+            expected: &["ddongJengE", "httpServer"],
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "invalid Camel value \"nope\", expected one of: ddongJengE, httpServer"
+    );
+    let err: Box<dyn std::error::Error> = Box::new(err);
+    assert!(err.to_string().starts_with("invalid Camel"));
+}
@@ -0,0 +1,124 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::hash::{Hash, Hasher};
+
+// 바이트열 키(JS 의 Buffer/Uint8Array 느낌). &[u8] 로 조회할 수 있고, 출력은 UTF-8 로 읽는다
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct JsBytes(pub Box<[u8]>);
+
+impl Borrow<[u8]> for JsBytes {
+    fn borrow(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<&[u8]> for JsBytes {
+    fn from(bytes: &[u8]) -> Self {
+        JsBytes(Box::from(bytes))
+    }
+}
+
+impl From<Vec<u8>> for JsBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        JsBytes(bytes.into_boxed_slice())
+    }
+}
+
+impl From<&str> for JsBytes {
+    fn from(text: &str) -> Self {
+        JsBytes::from(text.as_bytes())
+    }
+}
+
+impl Display for JsBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&String::from_utf8_lossy(&self.0))
+    }
+}
+
+// 숫자 키. JS Map 처럼 SameValueZero 로 비교한다: NaN 은 NaN 과 같고 +0 과 -0 은 같은 키다.
+// Eq/Hash/Ord 는 모두 정규화한 값(NaN 하나, +0)으로 계산해서 서로 어긋나지 않는다
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsNumber(pub f64);
+
+impl JsNumber {
+    pub fn value(self) -> f64 {
+        self.0
+    }
+
+    fn canonical(self) -> f64 {
+        if self.0.is_nan() {
+            f64::NAN
+        } else if self.0 == 0.0 {
+            0.0
+        } else {
+            self.0
+        }
+    }
+}
+
+impl PartialEq for JsNumber {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for JsNumber {}
+
+impl Hash for JsNumber {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical().to_bits().hash(state)
+    }
+}
+
+// -Infinity < ... < -0 = +0 < ... < Infinity < NaN
+impl Ord for JsNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical().total_cmp(&other.canonical())
+    }
+}
+
+impl PartialOrd for JsNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<f64> for JsNumber {
+    fn from(value: f64) -> Self {
+        JsNumber(value)
+    }
+}
+
+impl From<i32> for JsNumber {
+    fn from(value: i32) -> Self {
+        JsNumber(value.into())
+    }
+}
+
+impl From<JsNumber> for f64 {
+    fn from(number: JsNumber) -> Self {
+        number.0
+    }
+}
+
+// JS 의 Number.prototype.toString 과 같은 모양. 1 -> "1", -0 -> "0", 1e21 -> "1e+21"
+impl Display for JsNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.canonical();
+        if value.is_nan() {
+            f.write_str("NaN")
+        } else if value.is_infinite() {
+            f.write_str(if value > 0.0 { "Infinity" } else { "-Infinity" })
+        } else if value != 0.0 && !(1e-6..1e21).contains(&value.abs()) {
+            let text = format!("{value:e}");
+            match text.split_once('e') {
+                Some((mantissa, exp)) if !exp.starts_with('-') => write!(f, "{mantissa}e+{exp}"),
+                _ => f.write_str(&text),
+            }
+        } else {
+            write!(f, "{value}")
+        }
+    }
+}
@@ -1,2 +1,5 @@
+pub mod key;
+pub mod ordered;
 pub mod version_1;
 pub mod version_2;
+pub mod version_3;
@@ -0,0 +1,247 @@
+use super::{version_1, version_2, version_3};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::ControlFlow;
+use std::{iter, slice};
+
+// JS `Map`처럼 삽입 순서를 기억하는 맵. 조회는 index(HashMap)로 O(1),
+// 순회는 slots(Vec) 순서를 따른다. delete는 자리를 None으로 비워두고(tombstone)
+// 빈 자리가 절반을 넘으면 한 번에 당겨서 정리한다.
+#[derive(Debug, Clone)]
+pub struct OrderedJsMap<K, V> {
+    index: HashMap<K, usize>,
+    slots: Vec<Option<(K, V)>>,
+}
+
+type Slot<K, V> = Option<(K, V)>;
+pub type OrderedEntries<'a, K, V> =
+    iter::FilterMap<slice::Iter<'a, Slot<K, V>>, fn(&'a Slot<K, V>) -> Option<(&'a K, &'a V)>>;
+
+impl<K, V> OrderedJsMap<K, V> {
+    pub fn new() -> Self {
+        Self {
+            index: HashMap::new(),
+            slots: Vec::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    fn iter(&self) -> OrderedEntries<'_, K, V> {
+        self.slots
+            .iter()
+            .filter_map(|slot| slot.as_ref().map(|(k, v)| (k, v)))
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> OrderedJsMap<K, V> {
+    fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        let idx = *self.index.get(key)?;
+        self.slots[idx].as_ref().map(|(_, v)| v)
+    }
+
+    // 기존 키면 값만 교체(위치 유지), 새 키면 맨 뒤에 추가
+    fn insert(&mut self, key: K, value: V) {
+        match self.index.get(&key) {
+            Some(&idx) => {
+                if let Some((_, v)) = &mut self.slots[idx] {
+                    *v = value;
+                }
+            }
+            None => {
+                self.index.insert(key.clone(), self.slots.len());
+                self.slots.push(Some((key, value)));
+            }
+        }
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        let idx = self.index.remove(key)?;
+        let (_, value) = self.slots[idx].take()?;
+        if self.slots.len() > 2 * self.index.len() {
+            self.compact();
+        }
+        Some(value)
+    }
+
+    fn compact(&mut self) {
+        self.slots.retain(Option::is_some);
+        for (idx, (k, _)) in self.slots.iter().flatten().enumerate() {
+            if let Some(slot) = self.index.get_mut(k) {
+                *slot = idx;
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.index.clear();
+        self.slots.clear();
+    }
+}
+
+impl<K, V> Default for OrderedJsMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> FromIterator<(K, V)> for OrderedJsMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (k, v) in iter {
+            map.insert(k, v);
+        }
+        map
+    }
+}
+
+////////////////////////////////////////////////////////////////
+impl<'a, K, V> version_1::JsMap<'a> for OrderedJsMap<K, V>
+where
+    K: version_1::JsKey + Clone + 'a,
+    V: version_1::JsValue + 'a,
+{
+    type Key = K;
+    type Value = V;
+    type EntryIter = OrderedEntries<'a, K, V>;
+
+    fn get_value<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        self.get(key)
+    }
+
+    fn entries(&'a self) -> Self::EntryIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> version_1::JsMapMut<'a> for OrderedJsMap<K, V>
+where
+    K: version_1::JsKey + Clone + 'a,
+    V: version_1::JsValue + 'a,
+{
+    fn set(&mut self, key: K, value: V) -> &mut Self {
+        self.insert(key, value);
+        self
+    }
+
+    fn delete<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        self.remove(key).is_some()
+    }
+
+    fn clear(&mut self) {
+        OrderedJsMap::clear(self)
+    }
+
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
+////////////////////////////////////////////////////////////////
+impl<K: version_2::JsKey, V: version_2::JsValue> version_2::JsMap<K, V> for OrderedJsMap<K, V> {
+    fn get_value(&self, key: &K::Lookup) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn try_for_each_entry<'a>(
+        &'a self,
+        f: &mut dyn FnMut(&'a K, &'a V) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.iter().try_for_each(|(k, v)| f(k, v))
+    }
+
+    fn entries(&self) -> version_2::JsMapIterator<'_, K, V> {
+        Box::new(self.iter())
+    }
+}
+
+impl<K: version_2::JsKey, V: version_2::JsValue> version_2::JsMapMut<K, V> for OrderedJsMap<K, V> {
+    fn set(&mut self, key: K, value: V) -> &mut dyn version_2::JsMapMut<K, V> {
+        self.insert(key, value);
+        self
+    }
+
+    fn delete(&mut self, key: &K::Lookup) -> bool {
+        self.remove(key).is_some()
+    }
+
+    fn clear(&mut self) {
+        OrderedJsMap::clear(self)
+    }
+
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
+////////////////////////////////////////////////////////////////
+impl<K, V> version_3::JsMap for OrderedJsMap<K, V>
+where
+    K: version_3::JsKey + Clone,
+    V: version_3::JsValue,
+{
+    type Key = K;
+    type Value = V;
+    type EntryIter<'a>
+        = OrderedEntries<'a, K, V>
+    where
+        Self: 'a;
+
+    fn get_value<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        self.get(key)
+    }
+
+    fn entries(&self) -> Self::EntryIter<'_> {
+        self.iter()
+    }
+}
+
+impl<K, V> version_3::JsMapMut for OrderedJsMap<K, V>
+where
+    K: version_3::JsKey + Clone,
+    V: version_3::JsValue,
+{
+    fn set(&mut self, key: K, value: V) -> &mut Self {
+        self.insert(key, value);
+        self
+    }
+
+    fn delete<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        self.remove(key).is_some()
+    }
+
+    fn clear(&mut self) {
+        OrderedJsMap::clear(self)
+    }
+
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
@@ -1,3 +1,6 @@
+use super::key::{JsBytes, JsNumber};
+use super::ordered::OrderedJsMap;
+use std::borrow::Borrow;
 use std::collections::hash_map::{self, HashMap};
 use std::iter;
 use std::{
@@ -9,17 +12,40 @@ pub trait JsMap<'a> {
     type Value: JsValue + 'a;
     type EntryIter: Iterator<Item = (&'a Self::Key, &'a Self::Value)>;
 
-    fn get_value(&self, key: &Self::Key) -> Option<&Self::Value>;
+    // 키를 빌린 모양(String 이면 &str)으로도 찾는다. 조회하려고 키를 새로 만들 필요가 없다
+    fn get_value<Q>(&self, key: &Q) -> Option<&Self::Value>
+    where
+        Self::Key: Borrow<Q>,
+        Q: ?Sized + Hash + Ord;
 
     fn entries(&'a self) -> Self::EntryIter;
 
-    fn keys(&'a self) -> Keys<Self::EntryIter, Self::Key> {
+    fn keys(&'a self) -> Keys<'a, Self::EntryIter, Self::Key> {
         Keys {
             inner: self.entries(),
             mapper: |(k, _)| k,
         }
     }
 }
+// JS `Map`의 변경 API. `set`은 기존 키면 값만 바꾸고 위치는 유지한다.
+pub trait JsMapMut<'a>: JsMap<'a> {
+    fn set(&mut self, key: Self::Key, value: Self::Value) -> &mut Self;
+    // get_value 와 같이 키를 빌린 모양으로도 지운다
+    fn delete<Q>(&mut self, key: &Q) -> bool
+    where
+        Self::Key: Borrow<Q>,
+        Q: ?Sized + Hash + Ord;
+    fn clear(&mut self);
+    fn size(&self) -> usize;
+
+    fn has<Q>(&self, key: &Q) -> bool
+    where
+        Self::Key: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        self.get_value(key).is_some()
+    }
+}
 pub trait JsKey: Eq + Hash + Ord + Debug + Display {}
 impl JsKey for str {}
 impl JsKey for String {}
@@ -27,6 +53,8 @@ impl JsKey for usize {}
 impl JsKey for u8 {}
 impl JsKey for isize {}
 impl JsKey for i32 {}
+impl JsKey for JsBytes {}
+impl JsKey for JsNumber {}
 impl<T: JsKey> JsKey for &T {}
 // impl<T> JsKey for T where T: Sized + Deref<Target = str> {}
 impl<T: ?Sized + JsKey> JsKey for Box<T> {}
@@ -53,10 +81,12 @@ impl<'a, K: JsKey + 'a, V: JsValue + 'a> JsMap<'a> for Vec<(K, V)> {
     type Value = V;
     type EntryIter = iter::Map<std::slice::Iter<'a, (K, V)>, fn(&'a (K, V)) -> (&'a K, &'a V)>;
 
-    fn get_value(&self, key: &K) -> Option<&V> {
-        self.iter()
-            .find(|&(k, _)| k == key)
-            .and_then(|(_, e)| Some(e))
+    fn get_value<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        self.iter().find(|(k, _)| k.borrow() == key).map(|(_, e)| e)
     }
 
     fn entries(&'a self) -> Self::EntryIter {
@@ -64,14 +94,50 @@ impl<'a, K: JsKey + 'a, V: JsValue + 'a> JsMap<'a> for Vec<(K, V)> {
     }
 }
 
+impl<'a, K: JsKey + 'a, V: JsValue + 'a> JsMapMut<'a> for Vec<(K, V)> {
+    fn set(&mut self, key: K, value: V) -> &mut Self {
+        match self.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => *v = value,
+            None => self.push((key, value)),
+        }
+        self
+    }
+
+    fn delete<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        match self.iter().position(|(k, _)| k.borrow() == key) {
+            Some(idx) => {
+                self.remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
 ////////////////////////////////////////////////////////////////
 impl<'a, K: JsKey + 'a, V: JsValue + 'a> JsMap<'a> for HashMap<K, V> {
     type Key = K;
     type Value = V;
     type EntryIter = hash_map::Iter<'a, K, V>;
 
-    fn get_value(&self, key: &K) -> Option<&V> {
-        self.get(&key)
+    fn get_value<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        self.get(key)
     }
 
     fn entries(&'a self) -> Self::EntryIter {
@@ -79,10 +145,76 @@ impl<'a, K: JsKey + 'a, V: JsValue + 'a> JsMap<'a> for HashMap<K, V> {
     }
 }
 
+impl<'a, K: JsKey + 'a, V: JsValue + 'a> JsMapMut<'a> for HashMap<K, V> {
+    fn set(&mut self, key: K, value: V) -> &mut Self {
+        self.insert(key, value);
+        self
+    }
+
+    fn delete<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        self.remove(key).is_some()
+    }
+
+    fn clear(&mut self) {
+        HashMap::clear(self)
+    }
+
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
+////////////////////////////////////////////////////////////////
+// Box 로 감싼 맵(특히 Box<dyn version_2::JsMap>)도 그대로 version_1 맵으로 쓴다
+impl<'a, M: ?Sized + JsMap<'a>> JsMap<'a> for Box<M> {
+    type Key = M::Key;
+    type Value = M::Value;
+    type EntryIter = M::EntryIter;
+
+    fn get_value<Q>(&self, key: &Q) -> Option<&Self::Value>
+    where
+        Self::Key: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        (**self).get_value(key)
+    }
+
+    fn entries(&'a self) -> Self::EntryIter {
+        (**self).entries()
+    }
+}
+
+impl<'a, M: ?Sized + JsMapMut<'a>> JsMapMut<'a> for Box<M> {
+    fn set(&mut self, key: Self::Key, value: Self::Value) -> &mut Self {
+        (**self).set(key, value);
+        self
+    }
+
+    fn delete<Q>(&mut self, key: &Q) -> bool
+    where
+        Self::Key: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        (**self).delete(key)
+    }
+
+    fn clear(&mut self) {
+        (**self).clear()
+    }
+
+    fn size(&self) -> usize {
+        (**self).size()
+    }
+}
+
 ////////////////////////////////////////////////////////////////
 // #[test]
 pub fn test() {
-    let vec_boxstr_str: Vec<(Box<str>, _)> = vec![
+    let mut vec_boxstr_str: Vec<(Box<str>, _)> = vec![
         (Box::from("3ho"), "!!!!!!!!!"), //
         (Box::from("2ya"), "~!~!~"),
         (Box::from("1mu"), "~~!"),
@@ -98,6 +230,38 @@ pub fn test() {
     test_js_map(&vec_boxstr_str);
     println!("\n~~~~HashMap<isize, &str>~~~~");
     test_js_map(&hm_isize_str);
+
+    println!("\n~~~~JsMapMut: Vec<(Box<str>, &str)>~~~~");
+    vec_boxstr_str
+        .set(Box::from("2ya"), "?!?!?")
+        .set(Box::from("0zero"), "...");
+    vec_boxstr_str.delete("1mu");
+    println!(
+        "size: {}, has 1mu: {}",
+        vec_boxstr_str.size(),
+        vec_boxstr_str.has("1mu")
+    );
+    test_js_map(&vec_boxstr_str);
+    JsMapMut::clear(&mut vec_boxstr_str);
+    println!("\ncleared size: {}", vec_boxstr_str.size());
+
+    println!("\n~~~~OrderedJsMap<isize, &str>~~~~");
+    let mut ordered_isize_str: OrderedJsMap<isize, _> = OrderedJsMap::from_iter([
+        (3, "ho"), //
+        (1, "mu"),
+        (2, "ya"),
+    ]);
+    ordered_isize_str.delete(&1);
+    ordered_isize_str.set(3, "ho!").set(1, "mu!");
+    test_js_map(&ordered_isize_str);
+
+    println!("\n~~~~Vec<(JsBytes, &str)>~~~~");
+    let vec_bytes_str = vec![
+        (JsBytes::from("mu"), "!"), //
+        (JsBytes::from("ya"), "?"),
+    ];
+    test_js_map(&vec_bytes_str);
+    println!("\nget b\"ya\": {:?}", vec_bytes_str.get_value(&b"ya"[..]));
 }
 pub fn test_js_map<'a>(map: &'a impl JsMap<'a>) {
     print!("\nentries: \n\t");
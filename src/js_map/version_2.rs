@@ -1,28 +1,88 @@
+use super::key::{JsBytes, JsNumber};
+use super::ordered::OrderedJsMap;
+use super::version_1;
+use std::borrow::Borrow;
 use std::collections::hash_map::HashMap;
 use std::{
     fmt::{Debug, Display},
     hash::Hash,
+    ops::ControlFlow,
 };
-type JsMapIterator<'a, K, V> = Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a>;
+pub type JsMapIterator<'a, K, V> = Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a>;
 pub trait JsMap<K: JsKey, V: JsValue> {
-    fn get_value(&self, key: &K) -> Option<&V>;
+    // 키를 빌린 모양(K::Lookup, String 이면 str)으로 찾는다.
+    // 제네릭 get_value::<Q> 는 trait object 로 부를 수 없어서 키 타입마다 한 가지 모양을 정해 둔다
+    fn get_value(&self, key: &K::Lookup) -> Option<&V>;
 
-    fn entries(&self) -> JsMapIterator<K, V>;
+    // 내부 순회. dyn JsMap 으로 불러도 힙 할당이 없다. f 가 Break 를 돌려주면 멈춘다
+    fn try_for_each_entry<'a>(
+        &'a self,
+        f: &mut dyn FnMut(&'a K, &'a V) -> ControlFlow<()>,
+    ) -> ControlFlow<()>;
 
-    fn keys(&self) -> Keys<JsMapIterator<K, V>, K> {
+    fn for_each_entry<'a>(&'a self, f: &mut dyn FnMut(&'a K, &'a V)) {
+        let _ = self.try_for_each_entry(&mut |k, v| {
+            f(k, v);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn for_each_key<'a>(&'a self, f: &mut dyn FnMut(&'a K))
+    where
+        V: 'a,
+    {
+        self.for_each_entry(&mut |k, _| f(k))
+    }
+
+    // 외부 iterator 가 꼭 필요할 때만. 호출할 때마다 Box 를 하나 할당한다
+    fn entries(&self) -> JsMapIterator<'_, K, V>;
+
+    fn keys(&self) -> Keys<'_, JsMapIterator<'_, K, V>, K> {
         Keys {
             inner: self.entries(),
             mapper: |(k, _)| k,
         }
     }
 }
-pub trait JsKey: Sized + Eq + Hash + Ord + Clone + Debug + Display {}
-impl JsKey for isize {}
-impl JsKey for &str {}
-impl JsKey for String {}
-impl JsKey for Box<str> {}
-impl JsKey for std::rc::Rc<str> {}
-impl JsKey for std::sync::Arc<str> {}
+// JS `Map`의 변경 API. `set`은 기존 키면 값만 바꾸고 위치는 유지한다.
+pub trait JsMapMut<K: JsKey, V: JsValue>: JsMap<K, V> {
+    fn set(&mut self, key: K, value: V) -> &mut dyn JsMapMut<K, V>;
+    fn delete(&mut self, key: &K::Lookup) -> bool;
+    fn clear(&mut self);
+    fn size(&self) -> usize;
+
+    fn has(&self, key: &K::Lookup) -> bool {
+        self.get_value(key).is_some()
+    }
+}
+// 새 키 타입은 Lookup 을 정해야 한다. 빌린 모양이 따로 없으면 `type Lookup = Self;`
+pub trait JsKey: Sized + Eq + Hash + Ord + Clone + Debug + Display + Borrow<Self::Lookup> {
+    type Lookup: ?Sized + Eq + Hash + Ord;
+}
+impl JsKey for isize {
+    type Lookup = isize;
+}
+impl JsKey for &str {
+    type Lookup = str;
+}
+impl JsKey for String {
+    type Lookup = str;
+}
+impl JsKey for Box<str> {
+    type Lookup = str;
+}
+impl JsKey for std::rc::Rc<str> {
+    type Lookup = str;
+}
+impl JsKey for std::sync::Arc<str> {
+    type Lookup = str;
+}
+impl JsKey for JsBytes {
+    type Lookup = [u8];
+}
+impl JsKey for JsNumber {
+    type Lookup = JsNumber;
+}
 pub trait JsValue: Sized + Debug + Display {}
 impl<T> JsValue for T where T: Sized + Debug + Display {}
 
@@ -39,28 +99,241 @@ impl<'a, MapIter: Iterator, Key> Iterator for Keys<'a, MapIter, Key> {
 
 ////////////////////////////////////////////////////////////////
 impl<K: JsKey, V: JsValue> JsMap<K, V> for Vec<(K, V)> {
-    fn get_value(&self, key: &K) -> Option<&V> {
-        self.iter()
-            .find(|&(k, _)| k == key)
-            .and_then(|(_, e)| Some(e))
+    fn get_value(&self, key: &K::Lookup) -> Option<&V> {
+        self.iter().find(|(k, _)| k.borrow() == key).map(|(_, e)| e)
+    }
+
+    fn try_for_each_entry<'a>(
+        &'a self,
+        f: &mut dyn FnMut(&'a K, &'a V) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.iter().try_for_each(|(k, v)| f(k, v))
     }
 
-    fn entries(&self) -> JsMapIterator<K, V> {
+    fn entries(&self) -> JsMapIterator<'_, K, V> {
         Box::new(self.iter().map(|(k, v)| (k, v)))
     }
 }
 
+impl<K: JsKey, V: JsValue> JsMapMut<K, V> for Vec<(K, V)> {
+    fn set(&mut self, key: K, value: V) -> &mut dyn JsMapMut<K, V> {
+        match self.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => *v = value,
+            None => self.push((key, value)),
+        }
+        self
+    }
+
+    fn delete(&mut self, key: &K::Lookup) -> bool {
+        match self.iter().position(|(k, _)| k.borrow() == key) {
+            Some(idx) => {
+                self.remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
 ////////////////////////////////////////////////////////////////
 impl<K: JsKey, V: JsValue> JsMap<K, V> for HashMap<K, V> {
-    fn get_value(&self, key: &K) -> Option<&V> {
-        self.get(&key)
+    fn get_value(&self, key: &K::Lookup) -> Option<&V> {
+        self.get(key)
     }
 
-    fn entries(&self) -> JsMapIterator<K, V> {
+    fn try_for_each_entry<'a>(
+        &'a self,
+        f: &mut dyn FnMut(&'a K, &'a V) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.iter().try_for_each(|(k, v)| f(k, v))
+    }
+
+    fn entries(&self) -> JsMapIterator<'_, K, V> {
         Box::new(self.iter())
     }
 }
 
+impl<K: JsKey, V: JsValue> JsMapMut<K, V> for HashMap<K, V> {
+    fn set(&mut self, key: K, value: V) -> &mut dyn JsMapMut<K, V> {
+        self.insert(key, value);
+        self
+    }
+
+    fn delete(&mut self, key: &K::Lookup) -> bool {
+        self.remove(key).is_some()
+    }
+
+    fn clear(&mut self) {
+        HashMap::clear(self)
+    }
+
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
+////////////////////////////////////////////////////////////////
+// version_1 맵을 version_2 trait object 로 쓴다. `Box::new(DynJsMap(map)) as Box<dyn JsMap<K, V>>`
+// (version_3::FromV1 은 같은 일을 GAT trait 쪽으로 한다)
+//
+// 제약: version_1 은 trait 에 lifetime 이 붙어 있어서 모든 'a 에 대해 구현된 맵,
+// 즉 키와 값이 'static 인 맵만 감쌀 수 있다. `Vec<(&'k String, V)>` 처럼 키를 빌린 맵은
+// 'k 보다 긴 'a 에 대해 version_1::JsMap<'a> 가 아니라서 못 감싼다.
+// (`&str` 은 애초에 version_1::JsKey 가 아니다. `&T` 는 T: Sized 일 때만 구현된다)
+// 이런 맵은 키를 String, Box<str>, Rc<str> 같은 소유 타입으로 바꾼 뒤 감싼다
+#[derive(Debug, Clone, Default)]
+pub struct DynJsMap<M>(pub M);
+
+impl<M, K, V> JsMap<K, V> for DynJsMap<M>
+where
+    M: for<'a> version_1::JsMap<'a, Key = K, Value = V>,
+    K: JsKey + version_1::JsKey,
+    V: JsValue,
+{
+    fn get_value(&self, key: &K::Lookup) -> Option<&V> {
+        self.0.get_value(key)
+    }
+
+    fn try_for_each_entry<'a>(
+        &'a self,
+        f: &mut dyn FnMut(&'a K, &'a V) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.0.entries().try_for_each(|(k, v)| f(k, v))
+    }
+
+    fn entries(&self) -> JsMapIterator<'_, K, V> {
+        Box::new(self.0.entries())
+    }
+}
+
+impl<M, K, V> JsMapMut<K, V> for DynJsMap<M>
+where
+    M: for<'a> version_1::JsMapMut<'a, Key = K, Value = V>,
+    K: JsKey + version_1::JsKey,
+    V: JsValue,
+{
+    fn set(&mut self, key: K, value: V) -> &mut dyn JsMapMut<K, V> {
+        self.0.set(key, value);
+        self
+    }
+
+    fn delete(&mut self, key: &K::Lookup) -> bool {
+        self.0.delete(key)
+    }
+
+    fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+}
+
+// version_1 의 get_value::<Q>, delete::<Q>. Q 는 trait object 너머로 넘길 수 없어서 순회하며 찾는다.
+// 원래 맵의 조회(HashMap 이면 O(1))가 필요하면 JsMap::get_value(&K::Lookup) 를 직접 부른다
+fn find_entry_v1<'m, K, V, Q>(map: &'m (dyn JsMap<K, V> + '_), key: &Q) -> Option<(&'m K, &'m V)>
+where
+    K: JsKey + Borrow<Q>,
+    V: JsValue,
+    Q: ?Sized + Eq,
+{
+    let mut found = None;
+    let _ = map.try_for_each_entry(&mut |k, v| {
+        if <K as Borrow<Q>>::borrow(k) == key {
+            found = Some((k, v));
+            return ControlFlow::Break(());
+        }
+        ControlFlow::Continue(())
+    });
+    found
+}
+
+// 반대 방향. trait object 자체가 version_1 맵이라 `&dyn JsMap`, `Box<dyn JsMap>` 을
+// version_1 제네릭 함수에 그대로 넘길 수 있다. entries 는 version_2 처럼 Box 를 하나 할당한다
+impl<'a, 's, K, V> version_1::JsMap<'a> for dyn JsMap<K, V> + 's
+where
+    K: JsKey + version_1::JsKey + 'a,
+    V: JsValue + 'a,
+{
+    type Key = K;
+    type Value = V;
+    type EntryIter = JsMapIterator<'a, K, V>;
+
+    fn get_value<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        find_entry_v1(self, key).map(|(_, v)| v)
+    }
+
+    fn entries(&'a self) -> Self::EntryIter {
+        JsMap::entries(self)
+    }
+}
+
+impl<'a, 's, K, V> version_1::JsMap<'a> for dyn JsMapMut<K, V> + 's
+where
+    K: JsKey + version_1::JsKey + 'a,
+    V: JsValue + 'a,
+{
+    type Key = K;
+    type Value = V;
+    type EntryIter = JsMapIterator<'a, K, V>;
+
+    fn get_value<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        find_entry_v1(self, key).map(|(_, v)| v)
+    }
+
+    fn entries(&'a self) -> Self::EntryIter {
+        JsMap::entries(self)
+    }
+}
+
+impl<'a, 's, K, V> version_1::JsMapMut<'a> for dyn JsMapMut<K, V> + 's
+where
+    K: JsKey + version_1::JsKey + 'a,
+    V: JsValue + 'a,
+{
+    fn set(&mut self, key: K, value: V) -> &mut Self {
+        JsMapMut::set(self, key, value);
+        self
+    }
+
+    // 찾은 키를 복제해서 K::Lookup 모양으로 지운다. 복제는 이 어댑터에서만 일어난다
+    fn delete<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        let Some(key) = find_entry_v1(self, key).map(|(k, _)| k.clone()) else {
+            return false;
+        };
+        JsMapMut::delete(self, <K as Borrow<K::Lookup>>::borrow(&key))
+    }
+
+    fn clear(&mut self) {
+        JsMapMut::clear(self)
+    }
+
+    fn size(&self) -> usize {
+        JsMapMut::size(self)
+    }
+}
+
 ////////////////////////////////////////////////////////////////
 // #[test]
 pub fn test() {
@@ -75,7 +348,7 @@ pub fn test() {
         (5, 243.6),
         (6, 2.436),
     ]);
-    let vec_str_str = vec![
+    let mut vec_str_str = vec![
         ("3ho", "!!!!!!!!!"), //
         ("2ya", "~!~!~"),
         ("1mu", "~~!"),
@@ -98,18 +371,61 @@ pub fn test() {
     test_str_str(&vec_str_str);
     println!("\n~~~~HashMap<&str, &str>~~~~");
     test_str_str(&hm_str_str);
+
+    println!("\n~~~~JsMapMut: Vec<(&str, &str)>~~~~");
+    test_js_map_mut(&mut vec_str_str);
+    test_str_str(&vec_str_str);
+    JsMapMut::clear(&mut vec_str_str);
+    println!("\ncleared size: {}", vec_str_str.size());
+
+    println!("\n~~~~OrderedJsMap<&str, &str>~~~~");
+    let mut ordered_str_str = OrderedJsMap::from_iter([
+        ("c", "ho"), //
+        ("a", "mu"),
+        ("b", "ya"),
+    ]);
+    test_js_map_mut(&mut ordered_str_str);
+    ordered_str_str.delete("a");
+    ordered_str_str.set("a", "mu!");
+    test_str_str(&ordered_str_str);
+
+    println!("\n~~~~Vec<Box<dyn JsMap<String, isize>>>~~~~");
+    let pairs = || [("mu", 1), ("ya", 2), ("ho", 3)].map(|(k, v)| (k.to_owned(), v));
+    let maps: Vec<Box<dyn JsMap<String, isize>>> = vec![
+        Box::new(Vec::from(pairs())),
+        Box::new(HashMap::from(pairs())),
+        Box::new(DynJsMap(OrderedJsMap::from_iter(pairs()))),
+    ];
+    for map in &maps {
+        test_js_map(map.as_ref());
+    }
+    println!("\n~~~~version_1::test_js_map(Box<dyn JsMap<String, isize>>)~~~~");
+    version_1::test_js_map(&maps[0]);
+
+    println!("\n~~~~OrderedJsMap<JsNumber, &str>~~~~");
+    let mut ordered_num_str = OrderedJsMap::new();
+    ordered_num_str
+        .set(JsNumber(1.0), "one")
+        .set(JsNumber(f64::NAN), "nan")
+        .set(JsNumber(0.0), "+0")
+        .set(JsNumber(-0.0), "-0")
+        .set(JsNumber(-f64::NAN), "nan!")
+        .set(JsNumber(1e21), "big");
+    test_js_map(&ordered_num_str);
+}
+pub fn test_js_map_mut(map: &mut dyn JsMapMut<&str, &str>) {
+    map.set("2ya", "?!?!?").set("0zero", "...");
+    map.delete("1mu");
+    println!("size: {}, has 1mu: {}", map.size(), map.has("1mu"));
 }
 pub fn test_js_map<K: JsKey, V: JsValue>(map: &dyn JsMap<K, V>) {
     print!("\nentries: \n\t");
-    for (k, v) in map.entries() {
-        print!("({k}, {v}), ");
-    }
+    map.for_each_entry(&mut |k, v| print!("({k}, {v}), "));
     print!("\nkeys: \n\t");
-    for key in map.keys() {
-        print!("{key}, ");
-    }
+    map.for_each_key(&mut |key| print!("{key}, "));
     print!("\n\nsorted_by_key: \n");
-    let mut new_vec: Vec<(&K, &V)> = map.entries().collect();
+    let mut new_vec: Vec<(&K, &V)> = Vec::new();
+    map.for_each_entry(&mut |k, v| new_vec.push((k, v)));
     new_vec.sort_by_key(|&(k, _)| k);
     for (k, v) in new_vec {
         println!("\t{k}: {v}");
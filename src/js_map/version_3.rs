@@ -0,0 +1,302 @@
+use super::ordered::OrderedJsMap;
+use super::version_1;
+pub use super::version_1::{JsKey, JsValue};
+use std::borrow::Borrow;
+use std::collections::hash_map::{self, HashMap};
+use std::hash::Hash;
+use std::{iter, slice};
+
+// version_1 과 같은 모양이지만 lifetime 이 trait 이 아니라 EntryIter 에 붙는다(GAT).
+// 그래서 `&impl JsMap` 만으로 충분하고, 구조체에 넣거나 `for<'a>` 없이 제네릭으로 받을 수 있다.
+pub trait JsMap {
+    type Key: JsKey;
+    type Value: JsValue;
+    type EntryIter<'a>: Iterator<Item = (&'a Self::Key, &'a Self::Value)>
+    where
+        Self: 'a;
+
+    // version_1 과 같이 키를 빌린 모양(String 이면 &str)으로도 찾는다
+    fn get_value<Q>(&self, key: &Q) -> Option<&Self::Value>
+    where
+        Self::Key: Borrow<Q>,
+        Q: ?Sized + Hash + Ord;
+
+    fn entries(&self) -> Self::EntryIter<'_>;
+
+    fn keys(&self) -> Keys<Self::EntryIter<'_>> {
+        Keys {
+            inner: self.entries(),
+        }
+    }
+}
+// JS `Map`의 변경 API. `set`은 기존 키면 값만 바꾸고 위치는 유지한다.
+pub trait JsMapMut: JsMap {
+    fn set(&mut self, key: Self::Key, value: Self::Value) -> &mut Self;
+    // get_value 와 같이 키를 빌린 모양으로도 지운다
+    fn delete<Q>(&mut self, key: &Q) -> bool
+    where
+        Self::Key: Borrow<Q>,
+        Q: ?Sized + Hash + Ord;
+    fn clear(&mut self);
+    fn size(&self) -> usize;
+
+    fn has<Q>(&self, key: &Q) -> bool
+    where
+        Self::Key: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        self.get_value(key).is_some()
+    }
+}
+
+pub struct Keys<I> {
+    inner: I,
+}
+impl<'a, K: 'a, V: 'a, I: Iterator<Item = (&'a K, &'a V)>> Iterator for Keys<I> {
+    type Item = &'a K;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, _)| k)
+    }
+}
+
+////////////////////////////////////////////////////////////////
+impl<K: JsKey, V: JsValue> JsMap for Vec<(K, V)> {
+    type Key = K;
+    type Value = V;
+    type EntryIter<'a>
+        = iter::Map<slice::Iter<'a, (K, V)>, fn(&'a (K, V)) -> (&'a K, &'a V)>
+    where
+        Self: 'a;
+
+    fn get_value<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        self.iter().find(|(k, _)| k.borrow() == key).map(|(_, e)| e)
+    }
+
+    fn entries(&self) -> Self::EntryIter<'_> {
+        self.iter().map(|(k, v)| (k, v))
+    }
+}
+
+impl<K: JsKey, V: JsValue> JsMapMut for Vec<(K, V)> {
+    fn set(&mut self, key: K, value: V) -> &mut Self {
+        match self.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => *v = value,
+            None => self.push((key, value)),
+        }
+        self
+    }
+
+    fn delete<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        match self.iter().position(|(k, _)| k.borrow() == key) {
+            Some(idx) => {
+                self.remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
+////////////////////////////////////////////////////////////////
+impl<K: JsKey, V: JsValue> JsMap for HashMap<K, V> {
+    type Key = K;
+    type Value = V;
+    type EntryIter<'a>
+        = hash_map::Iter<'a, K, V>
+    where
+        Self: 'a;
+
+    fn get_value<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        self.get(key)
+    }
+
+    fn entries(&self) -> Self::EntryIter<'_> {
+        self.iter()
+    }
+}
+
+impl<K: JsKey, V: JsValue> JsMapMut for HashMap<K, V> {
+    fn set(&mut self, key: K, value: V) -> &mut Self {
+        self.insert(key, value);
+        self
+    }
+
+    fn delete<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        self.remove(key).is_some()
+    }
+
+    fn clear(&mut self) {
+        HashMap::clear(self)
+    }
+
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
+////////////////////////////////////////////////////////////////
+// 마이그레이션용. version_1::JsMap 만 구현한 맵을 version_3 API 로 쓴다.
+// version_1 은 trait 에 lifetime 이 붙어 있어서 모든 'a 에 대해 구현된 맵(키/값이 'static)만 감쌀 수 있다.
+#[derive(Debug, Clone, Default)]
+pub struct FromV1<M>(pub M);
+
+impl<M, K, V> JsMap for FromV1<M>
+where
+    M: for<'a> version_1::JsMap<'a, Key = K, Value = V>,
+    K: JsKey,
+    V: JsValue,
+{
+    type Key = K;
+    type Value = V;
+    type EntryIter<'a>
+        = <M as version_1::JsMap<'a>>::EntryIter
+    where
+        Self: 'a;
+
+    fn get_value<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        self.0.get_value(key)
+    }
+
+    fn entries(&self) -> Self::EntryIter<'_> {
+        self.0.entries()
+    }
+}
+
+impl<M, K, V> JsMapMut for FromV1<M>
+where
+    M: for<'a> version_1::JsMapMut<'a, Key = K, Value = V>,
+    K: JsKey,
+    V: JsValue,
+{
+    fn set(&mut self, key: K, value: V) -> &mut Self {
+        self.0.set(key, value);
+        self
+    }
+
+    fn delete<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        self.0.delete(key)
+    }
+
+    fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+}
+
+////////////////////////////////////////////////////////////////
+// 맵을 필드로 들고 있는 예시. version_1 이었다면 구조체에도 'a 가 필요했다
+struct Labeled<M: JsMap> {
+    label: &'static str,
+    map: M,
+}
+
+impl<M: JsMap> Labeled<M> {
+    fn print(&self) {
+        println!("\n~~~~{}~~~~", self.label);
+        test_js_map(&self.map);
+    }
+}
+
+// #[test]
+pub fn test() {
+    let mut vec_boxstr_str: Vec<(Box<str>, _)> = vec![
+        (Box::from("3ho"), "!!!!!!!!!"), //
+        (Box::from("2ya"), "~!~!~"),
+        (Box::from("1mu"), "~~!"),
+    ];
+    let hm_isize_str: HashMap<isize, _> = HashMap::from_iter([
+        (1, "mu"), //
+        (2, "ya"),
+        (3, "ho"),
+    ]);
+
+    println!("test begin");
+    println!("\n~~~~Vec<(Box<str>, &str)>~~~~");
+    test_js_map(&vec_boxstr_str);
+    Labeled {
+        label: "HashMap<isize, &str>",
+        map: hm_isize_str,
+    }
+    .print();
+
+    println!("\n~~~~JsMapMut: Vec<(Box<str>, &str)>~~~~");
+    vec_boxstr_str
+        .set(Box::from("2ya"), "?!?!?")
+        .set(Box::from("0zero"), "...");
+    vec_boxstr_str.delete("1mu");
+    println!(
+        "size: {}, has 1mu: {}",
+        vec_boxstr_str.size(),
+        vec_boxstr_str.has("1mu")
+    );
+    test_js_map(&vec_boxstr_str);
+
+    println!("\n~~~~FromV1<OrderedJsMap<isize, &str>>~~~~");
+    let mut ordered_isize_str = FromV1(OrderedJsMap::from_iter([
+        (3, "ho"), //
+        (1, "mu"),
+        (2, "ya"),
+    ]));
+    ordered_isize_str.delete(&1);
+    ordered_isize_str.set(3, "ho!").set(1, "mu!");
+    test_js_map(&ordered_isize_str);
+    println!("\nlongest key: {:?}", longest_key(&ordered_isize_str));
+}
+
+// version_1 에서는 `&'a impl JsMap<'a>` 였다
+pub fn test_js_map(map: &impl JsMap) {
+    print!("\nentries: \n\t");
+    for (k, v) in map.entries() {
+        print!("({k}, {v}), ");
+    }
+    print!("\nkeys: \n\t");
+    for key in map.keys() {
+        print!("{key}, ");
+    }
+    print!("\n\nsorted_by_key: \n");
+    let mut new_vec: Vec<_> = map.entries().collect();
+    new_vec.sort_by_key(|&(k, _)| k);
+    for (k, v) in new_vec {
+        println!("\t{k}: {v}");
+    }
+}
+
+// 빌린 값을 돌려주는 함수도 lifetime 하나면 된다
+fn longest_key<M: JsMap>(map: &M) -> Option<&M::Key> {
+    map.keys().max_by_key(|key| key.to_string().len())
+}
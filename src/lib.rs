@@ -0,0 +1,4 @@
+#[cfg(feature = "js_map")]
+pub mod js_map;
+#[cfg(feature = "object")]
+pub mod object;
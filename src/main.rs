@@ -1,43 +1,113 @@
+use js_enum_derive::JsEnum;
 #[cfg(feature = "js_map")]
-#[path = "js_map/mod.rs"]
-mod feature;
-
-#[cfg(not(any(feature = "js_map", feature = "object")))]
-#[path = "other.rs"]
-mod feature;
+use trait_practice::js_map;
 #[cfg(feature = "object")]
-#[path = "object/mod.rs"]
-mod feature;
+use trait_practice::object;
 
-use feature::*;
+// cargo run -- <js-map|reward> [v1|v2|v3]
+// 인자가 없으면 빌드된 데모를 전부 실행한다.
 fn main() {
-    println!("\n[VERSION 1]");
-    version_1::test();
-    println!("\n[VERSION 2]");
-    version_2::test();
-    dbg!(Enum::try_from("crane"));
+    let mut args = std::env::args().skip(1);
+    let demo = match args.next().map(|arg| arg.parse::<Demo>()).transpose() {
+        Ok(demo) => demo,
+        Err(err) => return usage(err),
+    };
+    let version = match args.next().map(|arg| arg.parse::<Version>()).transpose() {
+        Ok(version) => version,
+        Err(err) => return usage(err),
+    };
+
+    let demos = demo.as_ref().map_or(Demo::ALL, std::slice::from_ref);
+    let versions = version.as_ref().map_or(Version::ALL, std::slice::from_ref);
+    let picked = demo.is_some() && version.is_some();
+    for &demo in demos {
+        for &version in versions {
+            match find_demo(demo, version) {
+                Some(run) => {
+                    println!("\n[{demo} {version}]");
+                    run();
+                }
+                // 하나를 콕 집어 달라고 했을 때만 실패로 끝내고, 전부 돌릴 때는 건너뛴다
+                None if picked => {
+                    eprintln!(
+                        "{demo} {version} is not available (no such demo, or its cargo feature is disabled)"
+                    );
+                    std::process::exit(2);
+                }
+                None => {}
+            }
+        }
+    }
+    if demo.is_none() {
+        test_enum();
+    }
+}
+
+fn usage(err: impl std::fmt::Display) {
+    eprintln!("{err}");
+    eprintln!(
+        "usage: trait-practice <{}> [{}]",
+        Demo::NAMES.join("|"),
+        Version::NAMES.join("|")
+    );
+    std::process::exit(2);
+}
+
+#[derive(Debug, Clone, Copy, JsEnum)]
+enum Demo {
+    #[js(rename = "js-map")]
+    JsMap,
+    #[js(rename = "reward")]
+    Reward,
+}
+
+#[derive(Debug, Clone, Copy, JsEnum)]
+#[js(rename_all = "snake_case", case_insensitive)]
+enum Version {
+    V1,
+    V2,
+    V3,
 }
-#[derive(Debug)]
+
+fn find_demo(demo: Demo, version: Version) -> Option<fn()> {
+    match (demo, version) {
+        #[cfg(feature = "js_map")]
+        (Demo::JsMap, Version::V1) => Some(js_map::version_1::test),
+        #[cfg(feature = "js_map")]
+        (Demo::JsMap, Version::V2) => Some(js_map::version_2::test),
+        #[cfg(feature = "js_map")]
+        (Demo::JsMap, Version::V3) => Some(js_map::version_3::test),
+        #[cfg(feature = "object")]
+        (Demo::Reward, Version::V1) => Some(object::version_1::test),
+        #[cfg(feature = "object")]
+        (Demo::Reward, Version::V2) => Some(object::version_2::test),
+        _ => None,
+    }
+}
+
+fn test_enum() {
+    let _ = dbg!(Enum::try_from("crane"));
+    let _ = dbg!("DDONGJENGE".parse::<Enum>());
+    if let Err(err) = Enum::try_from("dragon") {
+        println!("{err}");
+    }
+    println!(
+        "{:?}",
+        Enum::ALL.iter().map(Enum::to_string).collect::<Vec<_>>()
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsEnum)]
+#[js(rename_all = "snake_case", case_insensitive)]
 enum Enum {
     Asimo,
     Balaclava,
     Crane,
+    #[js(rename = "ddongJengE")]
     DdongJengE,
 }
-//이런건 매크로로 만드는 실습 하면 좋을 듯. derive 가능하게
-impl TryFrom<&str> for Enum {
-    type Error = ();
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value {
-            "asimo" => Ok(Enum::Asimo),
-            "balaclava" => Ok(Enum::Balaclava),
-            "ddongJengE" => Ok(Enum::DdongJengE),
-            "crane" => Ok(Enum::Crane),
-            _ => Err(()),
-        }
-    }
-}
 
+#[allow(dead_code)]
 trait Traitor {
     type Foo;
     type Bar;
@@ -47,15 +117,18 @@ trait Traitor {
     fn foo_bar(&self, n: &Self::Foo) -> Vec<Self::Bar>;
 }
 
+#[allow(dead_code)]
 fn funk(traitor: impl Traitor) -> bool {
     traitor.is_valid()
 }
 
+#[allow(dead_code)]
 fn bunk<T: Traitor<Foo = u8>>(traitor: T) -> bool {
     traitor.foo_bar(&234);
     traitor.is_valid()
 }
 
+#[allow(dead_code, unused_variables)]
 fn grunge<T: Traitor>(traitor: &T, f1: &T::Foo, f2: &T::Foo) -> u32 {
     let is_foo = traitor.foo_foo(f1, f2);
     let bar = traitor.foo_bar(f2);
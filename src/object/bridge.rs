@@ -0,0 +1,189 @@
+use crate::object::version_1::{
+    self, Avatar, Coupon, Gem, Item, Quest, RewardBundle, RewardObj, RewardVisitor, Unknown,
+};
+use crate::object::version_2;
+
+// version_1 (trait object) <-> version_2 (enum) 변환. 필드는 그대로 옮기므로 손실이 없다.
+impl From<version_2::Reward> for RewardObj {
+    fn from(reward: version_2::Reward) -> Self {
+        match reward {
+            version_2::Reward::Item {
+                item_type,
+                url,
+                name,
+                shelf_life,
+                delta,
+                code,
+                is_hidden,
+            } => Box::new(Item {
+                item_type,
+                url,
+                name,
+                shelf_life,
+                delta,
+                code,
+                is_hidden,
+            }),
+            version_2::Reward::Gem {
+                currency,
+                delta,
+                min,
+                max,
+                name,
+                shelf_life,
+                is_hidden,
+            } => Box::new(Gem {
+                currency,
+                delta,
+                min,
+                max,
+                name,
+                shelf_life,
+                is_hidden,
+            }),
+            version_2::Reward::Avatar {
+                cosmetic_id,
+                url,
+                name,
+                delta,
+                is_hidden,
+            } => Box::new(Avatar {
+                cosmetic_id,
+                url,
+                name,
+                delta,
+                is_hidden,
+            }),
+            version_2::Reward::Coupon {
+                coupon_type,
+                code,
+                name,
+                state,
+                shelf_life,
+                delta,
+                is_hidden,
+            } => Box::new(Coupon {
+                coupon_type,
+                code,
+                name,
+                state,
+                shelf_life,
+                delta,
+                is_hidden,
+            }),
+            version_2::Reward::Quest {
+                quest_id,
+                name,
+                required,
+                url,
+                code,
+                shelf_life,
+                is_hidden,
+            } => Box::new(Quest {
+                quest_id,
+                name,
+                required,
+                url,
+                code,
+                shelf_life,
+                is_hidden,
+            }),
+            version_2::Reward::Bundle {
+                name,
+                rewards,
+                is_hidden,
+            } => Box::new(RewardBundle {
+                name,
+                rewards: rewards.into_iter().map(RewardObj::from).collect(),
+                is_hidden,
+            }),
+            version_2::Reward::Unknown => Box::new(Unknown),
+        }
+    }
+}
+
+impl From<&dyn version_1::Reward> for version_2::Reward {
+    fn from(reward: &dyn version_1::Reward) -> Self {
+        let mut visitor = IntoV2(version_2::Reward::Unknown);
+        reward.accept(&mut visitor);
+        visitor.0
+    }
+}
+
+impl From<RewardObj> for version_2::Reward {
+    fn from(reward: RewardObj) -> Self {
+        Self::from(&*reward as &dyn version_1::Reward)
+    }
+}
+
+struct IntoV2(version_2::Reward);
+
+impl RewardVisitor for IntoV2 {
+    fn visit_unknown(&mut self, _: &Unknown) {
+        self.0 = version_2::Reward::Unknown;
+    }
+    fn visit_gem(&mut self, gem: &Gem) {
+        self.0 = version_2::Reward::Gem {
+            currency: gem.currency.clone(),
+            delta: gem.delta,
+            min: gem.min,
+            max: gem.max,
+            name: gem.name.clone(),
+            shelf_life: gem.shelf_life,
+            is_hidden: gem.is_hidden,
+        };
+    }
+    fn visit_item(&mut self, item: &Item) {
+        self.0 = version_2::Reward::Item {
+            item_type: item.item_type.clone(),
+            url: item.url.clone(),
+            name: item.name.clone(),
+            shelf_life: item.shelf_life,
+            delta: item.delta,
+            code: item.code.clone(),
+            is_hidden: item.is_hidden,
+        };
+    }
+    fn visit_avatar(&mut self, avatar: &Avatar) {
+        self.0 = version_2::Reward::Avatar {
+            cosmetic_id: avatar.cosmetic_id.clone(),
+            url: avatar.url.clone(),
+            name: avatar.name.clone(),
+            delta: avatar.delta,
+            is_hidden: avatar.is_hidden,
+        };
+    }
+    fn visit_coupon(&mut self, coupon: &Coupon) {
+        self.0 = version_2::Reward::Coupon {
+            coupon_type: coupon.coupon_type.clone(),
+            code: coupon.code.clone(),
+            name: coupon.name.clone(),
+            state: coupon.state,
+            shelf_life: coupon.shelf_life,
+            delta: coupon.delta,
+            is_hidden: coupon.is_hidden,
+        };
+    }
+    fn visit_quest(&mut self, quest: &Quest) {
+        self.0 = version_2::Reward::Quest {
+            quest_id: quest.quest_id.clone(),
+            name: quest.name.clone(),
+            required: quest.required,
+            url: quest.url.clone(),
+            code: quest.code.clone(),
+            shelf_life: quest.shelf_life,
+            is_hidden: quest.is_hidden,
+        };
+    }
+    fn visit_bundle(&mut self, bundle: &RewardBundle) {
+        self.0 = version_2::Reward::Bundle {
+            name: bundle.name.clone(),
+            rewards: bundle
+                .rewards
+                .iter()
+                .map(|reward| version_2::Reward::from(&**reward as &dyn version_1::Reward))
+                .collect(),
+            is_hidden: bundle.is_hidden,
+        };
+    }
+}
@@ -0,0 +1,347 @@
+use crate::object::clock::{Clock, SystemClock};
+use crate::object::reward::{RewardDto, RewardGroup};
+use crate::object::rng::RewardRng;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+// Crockford base32. 헷갈리는 I, L, O, U 는 쓰지 않는다
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const CODE_LEN: usize = 12;
+// issue 가 새 코드를 찾는 횟수. 32^11 개 중에서 이만큼 연달아 겹치면 rng 나 저장소가 이상한 것이다
+const MAX_ISSUE_ATTEMPTS: u32 = 16;
+
+// 쿠폰 코드. 11 자리 랜덤 + 1 자리 체크섬(Luhn mod 32), "ABCD-EFGH-JKLM" 로 보여 준다
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CouponCode(String);
+
+impl CouponCode {
+    pub fn generate(rng: &mut dyn RewardRng) -> Self {
+        let mut code: String = (1..CODE_LEN)
+            .map(|_| ALPHABET[rng.range_inclusive(0, 31) as usize] as char)
+            .collect();
+        code.push(check_char(&code));
+        CouponCode(code)
+    }
+
+    // 대소문자, '-' 와 공백은 가리지 않는다
+    pub fn parse(input: &str) -> Result<Self, RedeemError> {
+        let code: String = input
+            .chars()
+            .filter(|c| *c != '-' && !c.is_whitespace())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        let invalid = || RedeemError::InvalidCode(input.to_owned());
+        if code.len() != CODE_LEN || !code.bytes().all(|b| ALPHABET.contains(&b)) {
+            return Err(invalid());
+        }
+        let (body, check) = code.split_at(CODE_LEN - 1);
+        if !check.starts_with(check_char(body)) {
+            return Err(invalid());
+        }
+        Ok(CouponCode(code))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for CouponCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let groups: Vec<&str> = [0..4, 4..8, 8..CODE_LEN]
+            .into_iter()
+            .map(|range| &self.0[range])
+            .collect();
+        f.write_str(&groups.join("-"))
+    }
+}
+
+// Luhn mod N. 한 글자 오타와 (거의 모든) 이웃한 두 글자 자리바꿈을 잡는다
+fn check_char(body: &str) -> char {
+    let sum: usize = body
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(i, b)| {
+            let value = ALPHABET.iter().position(|&a| a == b).unwrap_or_default();
+            let value = if i % 2 == 0 { value * 2 } else { value };
+            value / 32 + value % 32
+        })
+        .sum();
+    ALPHABET[(32 - sum % 32) % 32] as char
+}
+
+// 코드 하나를 몇 번 쓸 수 있는지
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Uses {
+    #[default]
+    Single,
+    Limited(u32),
+    Unlimited,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CouponRules {
+    pub uses: Uses,
+    // 한 플레이어가 쓸 수 있는 횟수. None 이면 uses 만 본다
+    pub per_player: Option<u32>,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CouponCampaign {
+    pub code: CouponCode,
+    pub rules: CouponRules,
+    // 사용에 성공하면 지급할 보상
+    pub rewards: Vec<RewardDto>,
+}
+
+impl CouponCampaign {
+    // 이 코드를 담은 COUPON 보상. 보상으로 받은 쿠폰을 나중에 redeem 한다
+    pub fn reward_dto(&self, coupon_type: impl Into<String>) -> RewardDto {
+        RewardDto {
+            _type: coupon_type.into(),
+            code: Some(self.code.to_string()),
+            shelf_life: self.rules.valid_until,
+            ..RewardDto::empty(RewardGroup::COUPON)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Redemption {
+    pub player: String,
+    pub at: DateTime<Utc>,
+}
+
+// 코드 하나에 남길 수 있는 사용 기록 수. None 이면 제한 없음
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RedeemLimits {
+    pub total: Option<u32>,
+    pub per_player: Option<u32>,
+}
+
+impl RedeemLimits {
+    pub fn of(rules: &CouponRules) -> Self {
+        match rules.uses {
+            Uses::Single => Self {
+                total: Some(1),
+                per_player: Some(1),
+            },
+            Uses::Limited(limit) => Self {
+                total: Some(limit),
+                per_player: rules.per_player,
+            },
+            Uses::Unlimited => Self {
+                total: None,
+                per_player: rules.per_player,
+            },
+        }
+    }
+
+    // 지금까지의 기록에 player 의 사용을 하나 더해도 되는지
+    pub fn check(&self, redemptions: &[Redemption], player: &str) -> Result<(), RedeemError> {
+        let by_player = redemptions.iter().filter(|r| r.player == player).count() as u32;
+        if self.per_player.is_some_and(|limit| by_player >= limit) {
+            return Err(RedeemError::AlreadyRedeemed {
+                player: player.to_owned(),
+            });
+        }
+        if self
+            .total
+            .is_some_and(|limit| redemptions.len() as u32 >= limit)
+        {
+            return Err(RedeemError::Exhausted);
+        }
+        Ok(())
+    }
+}
+
+// 쿠폰 저장소. 메모리 말고 DB 등을 붙일 때 구현한다
+pub trait CouponStore {
+    fn campaign(&self, code: &CouponCode) -> Option<CouponCampaign>;
+    // 이미 있는 코드면 false
+    fn insert(&mut self, campaign: CouponCampaign) -> bool;
+    fn redemptions(&self, code: &CouponCode) -> Vec<Redemption>;
+    // limits 안이면 기록한다. 확인과 기록 사이에 다른 사용이 끼어들면 안 되므로
+    // DB 구현은 한 트랜잭션(또는 조건부 INSERT)으로 처리한다
+    fn try_record(
+        &mut self,
+        code: &CouponCode,
+        redemption: Redemption,
+        limits: RedeemLimits,
+    ) -> Result<(), RedeemError>;
+
+    fn contains(&self, code: &CouponCode) -> bool {
+        self.campaign(code).is_some()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MemoryCouponStore {
+    campaigns: HashMap<CouponCode, CouponCampaign>,
+    redemptions: HashMap<CouponCode, Vec<Redemption>>,
+}
+
+impl MemoryCouponStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CouponStore for MemoryCouponStore {
+    fn campaign(&self, code: &CouponCode) -> Option<CouponCampaign> {
+        self.campaigns.get(code).cloned()
+    }
+    fn insert(&mut self, campaign: CouponCampaign) -> bool {
+        if self.campaigns.contains_key(&campaign.code) {
+            return false;
+        }
+        self.campaigns.insert(campaign.code.clone(), campaign);
+        true
+    }
+    fn redemptions(&self, code: &CouponCode) -> Vec<Redemption> {
+        self.redemptions.get(code).cloned().unwrap_or_default()
+    }
+    fn try_record(
+        &mut self,
+        code: &CouponCode,
+        redemption: Redemption,
+        limits: RedeemLimits,
+    ) -> Result<(), RedeemError> {
+        let redemptions = self.redemptions.entry(code.clone()).or_default();
+        limits.check(redemptions, &redemption.player)?;
+        redemptions.push(redemption);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RedeemError {
+    // 형식이 틀렸거나 체크섬이 맞지 않는 코드
+    InvalidCode(String),
+    NotFound(CouponCode),
+    NotYetValid { from: DateTime<Utc> },
+    Expired { at: DateTime<Utc> },
+    // 전체 사용 횟수를 다 썼다
+    Exhausted,
+    // 이 플레이어는 더 쓸 수 없다
+    AlreadyRedeemed { player: String },
+}
+
+impl Display for RedeemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RedeemError::InvalidCode(input) => write!(f, "invalid coupon code \"{input}\""),
+            RedeemError::NotFound(code) => write!(f, "coupon {code} not found"),
+            RedeemError::NotYetValid { from } => write!(f, "coupon is valid from {from}"),
+            RedeemError::Expired { at } => write!(f, "coupon expired at {at}"),
+            RedeemError::Exhausted => write!(f, "coupon has no uses left"),
+            RedeemError::AlreadyRedeemed { player } => {
+                write!(f, "coupon already redeemed by {player}")
+            }
+        }
+    }
+}
+
+impl Error for RedeemError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IssueError {
+    // 새 코드를 attempts 번 만들었는데 모두 저장소에 이미 있었다
+    CodeCollision { attempts: u32 },
+}
+
+impl Display for IssueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IssueError::CodeCollision { attempts } => {
+                write!(f, "no unused coupon code after {attempts} attempts")
+            }
+        }
+    }
+}
+
+impl Error for IssueError {}
+
+// 쿠폰 발급과 사용. 시각은 주입받은 clock 기준
+pub struct CouponService<S: CouponStore = MemoryCouponStore, C: Clock = SystemClock> {
+    pub store: S,
+    pub clock: C,
+}
+
+impl<S: CouponStore> CouponService<S> {
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            clock: SystemClock,
+        }
+    }
+}
+
+impl Default for CouponService {
+    fn default() -> Self {
+        Self::new(MemoryCouponStore::new())
+    }
+}
+
+impl<S: CouponStore, C: Clock> CouponService<S, C> {
+    pub fn with_clock<T: Clock>(self, clock: T) -> CouponService<S, T> {
+        CouponService {
+            store: self.store,
+            clock,
+        }
+    }
+
+    // 저장소에 없는 새 코드로 캠페인을 만든다. MAX_ISSUE_ATTEMPTS 번 모두 겹치면 오류
+    pub fn issue(
+        &mut self,
+        rng: &mut dyn RewardRng,
+        rules: CouponRules,
+        rewards: Vec<RewardDto>,
+    ) -> Result<CouponCampaign, IssueError> {
+        for _ in 0..MAX_ISSUE_ATTEMPTS {
+            let campaign = CouponCampaign {
+                code: CouponCode::generate(rng),
+                rules: rules.clone(),
+                rewards: rewards.clone(),
+            };
+            if self.store.insert(campaign.clone()) {
+                return Ok(campaign);
+            }
+        }
+        Err(IssueError::CodeCollision {
+            attempts: MAX_ISSUE_ATTEMPTS,
+        })
+    }
+
+    pub fn redeem(&mut self, code: &str, player: &str) -> Result<Vec<RewardDto>, RedeemError> {
+        let code = CouponCode::parse(code)?;
+        let campaign = self
+            .store
+            .campaign(&code)
+            .ok_or_else(|| RedeemError::NotFound(code.clone()))?;
+        let rules = &campaign.rules;
+        let now = self.clock.now();
+        if let Some(from) = rules.valid_from.filter(|from| now < *from) {
+            return Err(RedeemError::NotYetValid { from });
+        }
+        if let Some(at) = rules.valid_until.filter(|until| *until < now) {
+            return Err(RedeemError::Expired { at });
+        }
+
+        // 횟수 확인과 기록은 저장소가 한 번에 한다
+        self.store.try_record(
+            &code,
+            Redemption {
+                player: player.to_owned(),
+                at: now,
+            },
+            RedeemLimits::of(rules),
+        )?;
+        Ok(campaign.rewards)
+    }
+}
@@ -0,0 +1,122 @@
+use crate::object::reward::RewardGroup;
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+// RewardDto -> 보상 객체 변환 실패 사유.
+// 한 DTO에서 여러 필드가 잘못됐으면 Multiple 로 전부 모아서 돌려준다.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RewardError {
+    MissingField(&'static str),
+    // 그 보상 종류가 담지 않는 필드에 값이 있다. 조용히 버리지 않고 거절한다
+    UnexpectedField(&'static str),
+    UnknownGroup(String),
+    UnknownType { group: RewardGroup, _type: String },
+    InvalidRange { min: u32, max: u32 },
+    // LootTable::with_pity 가 가리킨 entry 가 테이블에 없다
+    UnknownPityEntry { table: String, entry: usize },
+    // LootTable::drop_rates 의 확률 분모가 u128 을 넘었다
+    ChanceOverflow { table: String },
+    Multiple(Vec<RewardError>),
+}
+
+impl RewardError {
+    // 검사 결과(None = 통과)를 모아서 하나도 없으면 Ok, 하나면 그대로, 여럿이면 Multiple
+    pub fn check(results: impl IntoIterator<Item = Option<RewardError>>) -> Result<(), Self> {
+        let mut errors: Vec<_> = results.into_iter().flatten().collect();
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            _ => Err(RewardError::Multiple(errors)),
+        }
+    }
+
+    pub fn missing<T>(field: &'static str, value: &Option<T>) -> Option<Self> {
+        value.is_none().then_some(RewardError::MissingField(field))
+    }
+
+    // 비어 있어야 하는 필드. None/0 이 아니면 오류
+    pub fn unexpected<T: Default + PartialEq>(field: &'static str, value: &T) -> Option<Self> {
+        (*value != T::default()).then_some(RewardError::UnexpectedField(field))
+    }
+
+    pub fn range(min: Option<u32>, max: Option<u32>) -> Option<Self> {
+        match (min, max) {
+            (Some(min), Some(max)) if min > max => Some(RewardError::InvalidRange { min, max }),
+            _ => None,
+        }
+    }
+
+    // Multiple 을 펼친 개별 오류 목록
+    pub fn errors(&self) -> Vec<&RewardError> {
+        match self {
+            RewardError::Multiple(errors) => errors.iter().flat_map(Self::errors).collect(),
+            err => vec![err],
+        }
+    }
+}
+
+impl Display for RewardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RewardError::MissingField(field) => write!(f, "missing field `{field}`"),
+            RewardError::UnexpectedField(field) => write!(f, "unexpected field `{field}`"),
+            RewardError::UnknownGroup(group) => write!(f, "unknown reward group {group:?}"),
+            RewardError::UnknownType { group, _type } => {
+                write!(f, "unknown _type {_type:?} for group {group:?}")
+            }
+            RewardError::InvalidRange { min, max } => {
+                write!(f, "invalid range: min {min} > max {max}")
+            }
+            RewardError::UnknownPityEntry { table, entry } => {
+                write!(
+                    f,
+                    "pity entry {entry} out of range for loot table {table:?}"
+                )
+            }
+            RewardError::ChanceOverflow { table } => {
+                write!(f, "drop chance overflows u128 in loot table {table:?}")
+            }
+            RewardError::Multiple(errors) => {
+                let messages: Vec<_> = errors.iter().map(ToString::to_string).collect();
+                write!(f, "{}", messages.join("; "))
+            }
+        }
+    }
+}
+
+impl Error for RewardError {}
+
+// 팩토리가 보상 생성에 실패했을 때 이유를 넘겨받는 곳. 로거/수집기를 주입한다.
+pub trait ErrorSink {
+    fn report(&self, err: &RewardError);
+}
+
+impl<F: Fn(&RewardError)> ErrorSink for F {
+    fn report(&self, err: &RewardError) {
+        self(err)
+    }
+}
+
+pub struct StdoutSink;
+impl ErrorSink for StdoutSink {
+    fn report(&self, err: &RewardError) {
+        println!("ERROR REPORT: {err} (RewardFactory::gen)");
+    }
+}
+
+#[derive(Default)]
+pub struct ErrorCollector {
+    errors: RefCell<Vec<RewardError>>,
+}
+impl ErrorCollector {
+    pub fn take(&self) -> Vec<RewardError> {
+        self.errors.take()
+    }
+}
+// 팩토리에 빌려주고 나중에 take 로 꺼내 본다
+impl ErrorSink for &ErrorCollector {
+    fn report(&self, err: &RewardError) {
+        self.errors.borrow_mut().push(err.clone())
+    }
+}
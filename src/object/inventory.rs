@@ -0,0 +1,190 @@
+use crate::object::version_1::{
+    Avatar, Coupon, Gem, Item, Quest, RewardBundle, RewardObj, RewardVisitor, Unknown,
+};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+// 플레이어 지갑(재화 잔액) + 아이템 보관함.
+// 같은 이름, 같은 유통기한의 아이템은 한 스택으로 합친다.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Inventory {
+    pub wallet: BTreeMap<String, u64>,
+    pub items: Vec<ItemStack>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemStack {
+    pub name: String,
+    pub url: String,
+    pub quantity: u64,
+    pub shelf_life: Option<DateTime<Utc>>,
+}
+
+// grant 로 바뀐 내용
+#[derive(Debug, Clone, PartialEq)]
+pub enum Granted {
+    Currency {
+        currency: String,
+        delta: u64,
+        balance: u64,
+    },
+    Item {
+        name: String,
+        quantity: u64,
+        shelf_life: Option<DateTime<Utc>>,
+    },
+    // 묶음 안의 보상들. 순서는 묶음에 담긴 순서
+    Bundle {
+        name: String,
+        granted: Vec<Granted>,
+    },
+    // 인벤토리에 들어가지 않는 보상 (Avatar, Quest, Unknown ...)
+    Skipped,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Receipt {
+    pub granted: Vec<Granted>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GrantError {
+    Overflow { target: String },
+    // min/max 범위가 남아 있는 Gem. Reward::roll 을 먼저 해야 한다
+    Unrolled { currency: String },
+}
+
+impl Display for GrantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GrantError::Overflow { target } => write!(f, "{target} overflowed"),
+            GrantError::Unrolled { currency } => {
+                write!(f, "{currency} amount has not been rolled yet")
+            }
+        }
+    }
+}
+
+impl Error for GrantError {}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn balance(&self, currency: &str) -> u64 {
+        self.wallet.get(currency).copied().unwrap_or_default()
+    }
+
+    fn add_currency(&mut self, currency: &str, delta: u64) -> Result<Granted, GrantError> {
+        let balance = self.wallet.entry(currency.to_owned()).or_default();
+        *balance = balance.checked_add(delta).ok_or(GrantError::Overflow {
+            target: currency.to_owned(),
+        })?;
+        Ok(Granted::Currency {
+            currency: currency.to_owned(),
+            delta,
+            balance: *balance,
+        })
+    }
+
+    fn add_item(
+        &mut self,
+        name: &str,
+        url: &str,
+        quantity: u64,
+        shelf_life: Option<DateTime<Utc>>,
+    ) -> Result<Granted, GrantError> {
+        match self
+            .items
+            .iter_mut()
+            .find(|stack| stack.name == name && stack.shelf_life == shelf_life)
+        {
+            Some(stack) => {
+                stack.quantity =
+                    stack
+                        .quantity
+                        .checked_add(quantity)
+                        .ok_or(GrantError::Overflow {
+                            target: name.to_owned(),
+                        })?
+            }
+            None => self.items.push(ItemStack {
+                name: name.to_owned(),
+                url: url.to_owned(),
+                quantity,
+                shelf_life,
+            }),
+        }
+        Ok(Granted::Item {
+            name: name.to_owned(),
+            quantity,
+            shelf_life,
+        })
+    }
+
+    fn apply(&mut self, reward: &RewardObj) -> Result<Granted, GrantError> {
+        let mut apply = Apply {
+            inventory: self,
+            granted: Ok(Granted::Skipped),
+        };
+        reward.accept(&mut apply);
+        apply.granted
+    }
+}
+
+// 보상 하나를 인벤토리에 반영한다. 인벤토리에 들어가지 않는 보상은 Skipped 그대로
+struct Apply<'a> {
+    inventory: &'a mut Inventory,
+    granted: Result<Granted, GrantError>,
+}
+
+impl RewardVisitor for Apply<'_> {
+    fn visit_unknown(&mut self, _: &Unknown) {}
+    fn visit_gem(&mut self, gem: &Gem) {
+        self.granted = match gem.range() {
+            Some(_) => Err(GrantError::Unrolled {
+                currency: gem.currency.clone(),
+            }),
+            None => self.inventory.add_currency(&gem.currency, gem.delta.into()),
+        }
+    }
+    fn visit_item(&mut self, item: &Item) {
+        self.granted =
+            self.inventory
+                .add_item(&item.name, &item.url, item.delta.into(), item.shelf_life)
+    }
+    fn visit_avatar(&mut self, _: &Avatar) {}
+    fn visit_coupon(&mut self, _: &Coupon) {}
+    fn visit_quest(&mut self, _: &Quest) {}
+    fn visit_bundle(&mut self, bundle: &RewardBundle) {
+        // 하나라도 실패하면 grant_all 이 사본을 버리므로 묶음 전체가 반영되지 않는다
+        let inventory = &mut *self.inventory;
+        self.granted = bundle
+            .rewards
+            .iter()
+            .map(|reward| inventory.apply(reward))
+            .collect::<Result<_, _>>()
+            .map(|granted| Granted::Bundle {
+                name: bundle.name.clone(),
+                granted,
+            });
+    }
+}
+
+pub fn grant(inventory: &mut Inventory, reward: &RewardObj) -> Result<Receipt, GrantError> {
+    grant_all(inventory, std::slice::from_ref(reward))
+}
+
+// 전부 반영되거나 하나도 반영되지 않는다. 사본에 적용해 보고 성공하면 바꿔 끼운다.
+pub fn grant_all(inventory: &mut Inventory, rewards: &[RewardObj]) -> Result<Receipt, GrantError> {
+    let mut staged = inventory.clone();
+    let granted = rewards
+        .iter()
+        .map(|reward| staged.apply(reward))
+        .collect::<Result<_, _>>()?;
+    *inventory = staged;
+    Ok(Receipt { granted })
+}
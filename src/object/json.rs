@@ -0,0 +1,195 @@
+use crate::object::reward::CouponState;
+use crate::object::version_1::{
+    Avatar, Coupon, Gem, Item, Quest, Reward, RewardBundle, RewardObj, RewardVisitor, Unknown,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt::{self, Display, Write};
+
+// 보상을 JSON 으로 내보낸다. serde 없이도 쓸 수 있도록 직접 쓴다.
+// 구조체 필드를 빠짐없이 쓴다.
+// {"kind":"gem","currency":"XP","delta":3000,"min":null,"max":null,"name":null,"shelfLife":null,"isHidden":false}
+pub fn to_json(reward: &dyn Reward) -> String {
+    let mut exporter = JsonExporter::default();
+    reward.accept(&mut exporter);
+    exporter.to_string()
+}
+
+pub fn to_json_array(rewards: &[RewardObj]) -> String {
+    let objects: Vec<String> = rewards.iter().map(|reward| to_json(&**reward)).collect();
+    format!("[{}]", objects.join(","))
+}
+
+enum Value {
+    Null,
+    Bool(bool),
+    Number(u32),
+    String(String),
+    // 이미 JSON 으로 쓴 값 (묶음의 rewards 배열)
+    Raw(String),
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+impl From<u32> for Value {
+    fn from(value: u32) -> Self {
+        Value::Number(value)
+    }
+}
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_owned())
+    }
+}
+impl From<&DateTime<Utc>> for Value {
+    fn from(value: &DateTime<Utc>) -> Self {
+        Value::String(value.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+}
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => f.write_str("null"),
+            Value::Bool(value) => write!(f, "{value}"),
+            Value::Number(value) => write!(f, "{value}"),
+            Value::Raw(json) => f.write_str(json),
+            Value::String(value) => {
+                f.write_char('"')?;
+                for c in value.chars() {
+                    match c {
+                        '"' => f.write_str("\\\"")?,
+                        '\\' => f.write_str("\\\\")?,
+                        '\n' => f.write_str("\\n")?,
+                        '\r' => f.write_str("\\r")?,
+                        '\t' => f.write_str("\\t")?,
+                        c if c < ' ' => write!(f, "\\u{:04x}", c as u32)?,
+                        c => f.write_char(c)?,
+                    }
+                }
+                f.write_char('"')
+            }
+        }
+    }
+}
+
+// 방문한 보상의 필드를 순서대로 모아 두었다가 객체 하나로 쓴다
+#[derive(Default)]
+struct JsonExporter {
+    fields: Vec<(&'static str, Value)>,
+}
+
+impl JsonExporter {
+    fn object(&mut self, kind: &str, fields: impl IntoIterator<Item = (&'static str, Value)>) {
+        self.fields = vec![("kind", kind.into())];
+        self.fields.extend(fields);
+    }
+}
+
+impl Display for JsonExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('{')?;
+        for (i, (key, value)) in self.fields.iter().enumerate() {
+            if i > 0 {
+                f.write_char(',')?;
+            }
+            write!(f, "\"{key}\":{value}")?;
+        }
+        f.write_char('}')
+    }
+}
+
+impl RewardVisitor for JsonExporter {
+    fn visit_unknown(&mut self, _: &Unknown) {
+        self.object("unknown", [])
+    }
+    fn visit_gem(&mut self, gem: &Gem) {
+        self.object(
+            "gem",
+            [
+                ("currency", gem.currency.as_str().into()),
+                ("delta", gem.delta.into()),
+                ("min", gem.min.into()),
+                ("max", gem.max.into()),
+                ("name", gem.name.as_deref().into()),
+                ("shelfLife", gem.shelf_life.as_ref().into()),
+                ("isHidden", gem.is_hidden.into()),
+            ],
+        )
+    }
+    fn visit_item(&mut self, item: &Item) {
+        self.object(
+            "item",
+            [
+                ("itemType", item.item_type.as_str().into()),
+                ("name", item.name.as_str().into()),
+                ("url", item.url.as_str().into()),
+                ("delta", item.delta.into()),
+                ("shelfLife", item.shelf_life.as_ref().into()),
+                ("code", item.code.as_deref().into()),
+                ("isHidden", item.is_hidden.into()),
+            ],
+        )
+    }
+    fn visit_avatar(&mut self, avatar: &Avatar) {
+        self.object(
+            "avatar",
+            [
+                ("cosmeticId", avatar.cosmetic_id.as_str().into()),
+                ("url", avatar.url.as_str().into()),
+                ("name", avatar.name.as_deref().into()),
+                ("delta", avatar.delta.into()),
+                ("isHidden", avatar.is_hidden.into()),
+            ],
+        )
+    }
+    fn visit_coupon(&mut self, coupon: &Coupon) {
+        let redeemed_at = match &coupon.state {
+            CouponState::Unredeemed => None,
+            CouponState::Redeemed(at) => Some(at),
+        };
+        self.object(
+            "coupon",
+            [
+                ("couponType", coupon.coupon_type.as_str().into()),
+                ("code", coupon.code.as_str().into()),
+                ("name", coupon.name.as_deref().into()),
+                ("redeemedAt", redeemed_at.into()),
+                ("shelfLife", coupon.shelf_life.as_ref().into()),
+                ("delta", coupon.delta.into()),
+                ("isHidden", coupon.is_hidden.into()),
+            ],
+        )
+    }
+    fn visit_quest(&mut self, quest: &Quest) {
+        self.object(
+            "quest",
+            [
+                ("questId", quest.quest_id.as_str().into()),
+                ("name", quest.name.as_str().into()),
+                ("required", quest.required.into()),
+                ("url", quest.url.as_deref().into()),
+                ("code", quest.code.as_deref().into()),
+                ("shelfLife", quest.shelf_life.as_ref().into()),
+                ("isHidden", quest.is_hidden.into()),
+            ],
+        )
+    }
+    fn visit_bundle(&mut self, bundle: &RewardBundle) {
+        self.object(
+            "bundle",
+            [
+                ("name", bundle.name.as_str().into()),
+                ("rewards", Value::Raw(to_json_array(&bundle.rewards))),
+                ("isHidden", bundle.is_hidden.into()),
+            ],
+        )
+    }
+}
@@ -0,0 +1,307 @@
+use crate::object::error::RewardError;
+use crate::object::reward::RewardDto;
+use crate::object::rng::RewardRng;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::ops::RangeInclusive;
+
+// 가챠/상자 드랍 테이블. 굴린 결과는 RewardDto 이므로 평소처럼 RewardFactory 에 넘긴다.
+//
+// 한 번 굴리면
+// 1. guaranteed 드랍이 전부 나오고
+// 2. entries 중 하나가 weight 비율로 뽑힌다. 천장(pity)이 차 있으면 그 entry 가 대신 나온다
+pub struct LootTable {
+    // 천장 카운터의 키. 표시용 name 이 바뀌거나 겹쳐도 카운터는 이어진다
+    pub id: String,
+    pub name: String,
+    pub guaranteed: Vec<LootDrop>,
+    pub entries: Vec<LootEntry>,
+    pub pity: Option<Pity>,
+}
+
+pub struct LootEntry {
+    pub weight: u32,
+    pub drop: LootDrop,
+}
+
+pub enum LootDrop {
+    // template 을 복사하고 delta 를 quantity 안에서 굴린다
+    Reward {
+        template: RewardDto,
+        quantity: RangeInclusive<u32>,
+    },
+    Table(Box<LootTable>),
+    Nothing,
+}
+
+// entries[entry] 가 after 번째 굴림까지 한 번도 안 나오면 그 굴림에서 확정으로 나온다
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pity {
+    pub entry: usize,
+    pub after: u32,
+}
+
+// 플레이어별 천장 카운터. 테이블 id -> 천장 entry 가 연속으로 안 나온 횟수
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PityCounters {
+    misses: HashMap<String, u32>,
+}
+
+impl PityCounters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn misses(&self, table_id: &str) -> u32 {
+        self.misses.get(table_id).copied().unwrap_or_default()
+    }
+}
+
+impl LootDrop {
+    // 5..=1 처럼 빈 quantity 는 InvalidRange
+    pub fn reward(template: RewardDto, quantity: RangeInclusive<u32>) -> Result<Self, RewardError> {
+        if let Some(err) = RewardError::range(Some(*quantity.start()), Some(*quantity.end())) {
+            return Err(err);
+        }
+        Ok(LootDrop::Reward { template, quantity })
+    }
+
+    pub fn table(table: LootTable) -> Self {
+        LootDrop::Table(Box::new(table))
+    }
+
+    fn roll_into(
+        &self,
+        rng: &mut dyn RewardRng,
+        pity: &mut PityCounters,
+        drops: &mut Vec<RewardDto>,
+    ) {
+        match self {
+            LootDrop::Reward { template, quantity } => drops.push(RewardDto {
+                delta: rng.range_inclusive(*quantity.start(), *quantity.end()),
+                ..template.clone()
+            }),
+            LootDrop::Table(table) => table.roll_into(rng, pity, drops),
+            LootDrop::Nothing => {}
+        }
+    }
+}
+
+impl LootTable {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            guaranteed: Vec::new(),
+            entries: Vec::new(),
+            pity: None,
+        }
+    }
+
+    pub fn with_guaranteed(mut self, drop: LootDrop) -> Self {
+        self.guaranteed.push(drop);
+        self
+    }
+
+    pub fn with_entry(mut self, weight: u32, drop: LootDrop) -> Self {
+        self.entries.push(LootEntry { weight, drop });
+        self
+    }
+
+    // entry 는 with_entry 로 넣은 순서(0부터). 없는 entry 면 UnknownPityEntry
+    pub fn with_pity(self, entry: usize, after: u32) -> Result<Self, RewardError> {
+        if entry >= self.entries.len() {
+            return Err(RewardError::UnknownPityEntry {
+                table: self.id,
+                entry,
+            });
+        }
+        Ok(Self {
+            pity: Some(Pity { entry, after }),
+            ..self
+        })
+    }
+
+    pub fn roll(&self, rng: &mut dyn RewardRng, pity: &mut PityCounters) -> Vec<RewardDto> {
+        let mut drops = Vec::new();
+        self.roll_into(rng, pity, &mut drops);
+        drops
+    }
+
+    fn roll_into(
+        &self,
+        rng: &mut dyn RewardRng,
+        pity: &mut PityCounters,
+        drops: &mut Vec<RewardDto>,
+    ) {
+        for drop in &self.guaranteed {
+            drop.roll_into(rng, pity, drops);
+        }
+        if let Some(index) = self.pick(rng, pity) {
+            self.entries[index].drop.roll_into(rng, pity, drops);
+        }
+    }
+
+    // 뽑힌 entry 의 index
+    fn pick(&self, rng: &mut dyn RewardRng, pity: &mut PityCounters) -> Option<usize> {
+        let Some(Pity { entry, after }) = self.pity else {
+            return self.pick_weighted(rng);
+        };
+        let misses = pity.misses.entry(self.id.clone()).or_default();
+        if *misses + 1 >= after {
+            *misses = 0;
+            return Some(entry);
+        }
+        let picked = self.pick_weighted(rng);
+        match picked == Some(entry) {
+            true => *misses = 0,
+            false => *misses += 1,
+        }
+        picked
+    }
+
+    fn pick_weighted(&self, rng: &mut dyn RewardRng) -> Option<usize> {
+        let total = self.total_weight();
+        if total == 0 {
+            return None;
+        }
+        // [0, total) 구간의 티켓. RewardRng::range_inclusive 와 같은 방식
+        let mut ticket = ((u128::from(rng.next_u64()) * u128::from(total)) >> 64) as u64;
+        self.entries.iter().position(|entry| {
+            let weight = u64::from(entry.weight);
+            if ticket < weight {
+                return true;
+            }
+            ticket -= weight;
+            false
+        })
+    }
+
+    fn total_weight(&self) -> u64 {
+        self.entries
+            .iter()
+            .map(|entry| u64::from(entry.weight))
+            .sum()
+    }
+
+    // 보상 entry 마다 한 번 굴렸을 때 나올 확률. 천장은 빼고 weight 만으로 계산한다.
+    // 같은 template 이 여러 곳에 있으면 따로 나온다.
+    // 큰 weight 를 깊게 중첩해서 분모가 u128 을 넘으면 ChanceOverflow
+    pub fn drop_rates(&self) -> Result<Vec<DropRate<'_>>, RewardError> {
+        let mut rates = Vec::new();
+        self.collect_rates(&mut Vec::new(), Chance::ONE, &mut rates)?;
+        Ok(rates)
+    }
+
+    fn collect_rates<'a>(
+        &'a self,
+        path: &mut Vec<&'a str>,
+        chance: Chance,
+        rates: &mut Vec<DropRate<'a>>,
+    ) -> Result<(), RewardError> {
+        path.push(&self.name);
+        for drop in &self.guaranteed {
+            drop.collect_rates(path, chance, rates)?;
+        }
+        let total = self.total_weight();
+        for entry in self.entries.iter().filter(|entry| entry.weight > 0) {
+            let chance = chance
+                .checked_mul(Chance::new(entry.weight.into(), total.into()))
+                .ok_or_else(|| RewardError::ChanceOverflow {
+                    table: self.id.clone(),
+                })?;
+            entry.drop.collect_rates(path, chance, rates)?;
+        }
+        path.pop();
+        Ok(())
+    }
+}
+
+impl LootDrop {
+    fn collect_rates<'a>(
+        &'a self,
+        path: &mut Vec<&'a str>,
+        chance: Chance,
+        rates: &mut Vec<DropRate<'a>>,
+    ) -> Result<(), RewardError> {
+        match self {
+            LootDrop::Reward { template, quantity } => rates.push(DropRate {
+                path: path.clone(),
+                template,
+                quantity: quantity.clone(),
+                chance,
+            }),
+            LootDrop::Table(table) => table.collect_rates(path, chance, rates)?,
+            LootDrop::Nothing => {}
+        }
+        Ok(())
+    }
+}
+
+pub struct DropRate<'a> {
+    // 바깥 테이블부터 이 보상이 들어 있는 테이블까지의 이름
+    pub path: Vec<&'a str>,
+    pub template: &'a RewardDto,
+    pub quantity: RangeInclusive<u32>,
+    pub chance: Chance,
+}
+
+// 기약분수로 들고 있는 정확한 확률
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Chance {
+    numerator: u128,
+    denominator: u128,
+}
+
+impl Chance {
+    pub const ONE: Chance = Chance {
+        numerator: 1,
+        denominator: 1,
+    };
+
+    // denominator 가 0 이면 panic
+    pub fn new(numerator: u128, denominator: u128) -> Self {
+        assert!(denominator != 0, "chance with zero denominator");
+        let gcd = gcd(numerator, denominator);
+        Self {
+            numerator: numerator / gcd,
+            denominator: denominator / gcd,
+        }
+    }
+
+    pub fn numerator(&self) -> u128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> u128 {
+        self.denominator
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    // 두 확률의 곱. 약분한 뒤에도 u128 을 넘으면 None
+    pub fn checked_mul(self, rhs: Chance) -> Option<Chance> {
+        // 먼저 엇갈려 약분해서 넘칠 일을 줄인다
+        let a = Chance::new(self.numerator, rhs.denominator);
+        let b = Chance::new(rhs.numerator, self.denominator);
+        Some(Chance::new(
+            a.numerator.checked_mul(b.numerator)?,
+            a.denominator.checked_mul(b.denominator)?,
+        ))
+    }
+}
+
+impl Display for Chance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.max(1)
+}
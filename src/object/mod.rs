@@ -1,3 +1,13 @@
+pub mod bridge;
+pub mod clock;
+pub mod coupon;
+pub mod error;
+pub mod inventory;
+pub mod json;
+pub mod loot;
+pub mod registry;
 pub mod reward;
+pub mod rng;
+pub mod sweeper;
 pub mod version_1;
 pub mod version_2;
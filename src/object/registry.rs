@@ -0,0 +1,92 @@
+use crate::object::error::RewardError;
+use crate::object::reward::{RewardDto, RewardGroup};
+use crate::object::version_1::{Avatar, Coupon, Gem, Item, Quest, RewardObj};
+use std::collections::HashMap;
+
+pub type RewardCtor = Box<dyn Fn(RewardDto) -> Result<RewardObj, RewardError> + Send + Sync>;
+pub type RewardMatcher = Box<dyn Fn(&RewardDto) -> bool + Send + Sync>;
+
+// (group, _type) -> 생성자. 게임 모듈이 시작할 때 자기 보상 종류를 등록한다.
+// 찾는 순서: 정확히 일치하는 (group, _type) -> 등록 순서대로 predicate
+#[derive(Default)]
+pub struct RewardRegistry {
+    exact: HashMap<(RewardGroup, String), RewardCtor>,
+    rules: Vec<(RewardMatcher, RewardCtor)>,
+}
+
+impl RewardRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 기본 보상 종류(ASSET -> Gem, ITEM -> Item, AVATAR, COUPON, QUEST)가 등록된 레지스트리
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        registry
+            .register_if(
+                |dto| dto.group == RewardGroup::ASSET,
+                |dto| Ok(Box::new(Gem::try_from(dto)?)),
+            )
+            .register_if(
+                |dto| dto.group == RewardGroup::ITEM,
+                |dto| Ok(Box::new(Item::try_from(dto)?)),
+            )
+            .register_if(
+                |dto| dto.group == RewardGroup::AVATAR,
+                |dto| Ok(Box::new(Avatar::try_from(dto)?)),
+            )
+            .register_if(
+                |dto| dto.group == RewardGroup::COUPON,
+                |dto| Ok(Box::new(Coupon::try_from(dto)?)),
+            )
+            .register_if(
+                |dto| dto.group == RewardGroup::QUEST,
+                |dto| Ok(Box::new(Quest::try_from(dto)?)),
+            );
+        registry
+    }
+
+    pub fn register<F>(
+        &mut self,
+        group: RewardGroup,
+        _type: impl Into<String>,
+        ctor: F,
+    ) -> &mut Self
+    where
+        F: Fn(RewardDto) -> Result<RewardObj, RewardError> + Send + Sync + 'static,
+    {
+        self.exact.insert((group, _type.into()), Box::new(ctor));
+        self
+    }
+
+    pub fn register_if<P, F>(&mut self, matcher: P, ctor: F) -> &mut Self
+    where
+        P: Fn(&RewardDto) -> bool + Send + Sync + 'static,
+        F: Fn(RewardDto) -> Result<RewardObj, RewardError> + Send + Sync + 'static,
+    {
+        self.rules.push((Box::new(matcher), Box::new(ctor)));
+        self
+    }
+
+    pub fn build(&self, dto: RewardDto) -> Result<RewardObj, RewardError> {
+        let ctor = self
+            .exact
+            .get(&(dto.group.clone(), dto._type.clone()))
+            .or_else(|| {
+                self.rules
+                    .iter()
+                    .find(|(matcher, _)| matcher(&dto))
+                    .map(|(_, ctor)| ctor)
+            });
+        match ctor {
+            Some(ctor) => ctor(dto),
+            None => Err(match dto.group {
+                RewardGroup::Unknown(group) => RewardError::UnknownGroup(group),
+                group => RewardError::UnknownType {
+                    group,
+                    _type: dto._type,
+                },
+            }),
+        }
+    }
+}
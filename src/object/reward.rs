@@ -1,23 +1,167 @@
+use crate::object::error::RewardError;
 use chrono::{DateTime, Utc};
-#[derive(Debug, Clone)]
-pub(crate) enum RewardGroup {
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "String", into = "String")
+)]
+pub enum RewardGroup {
     ASSET,
     AVATAR,
     ITEM,
     COUPON,
     QUEST,
+    // 서버가 새로 추가한 그룹. 버리지 않고 원래 문자열을 들고 있는다
+    Unknown(String),
 }
 
-#[derive(Clone)]
+impl From<String> for RewardGroup {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "ASSET" => RewardGroup::ASSET,
+            "AVATAR" => RewardGroup::AVATAR,
+            "ITEM" => RewardGroup::ITEM,
+            "COUPON" => RewardGroup::COUPON,
+            "QUEST" => RewardGroup::QUEST,
+            _ => RewardGroup::Unknown(value),
+        }
+    }
+}
+
+impl RewardGroup {
+    // 이 그룹의 보상으로 만들 수 있는 DTO 인지 확인한다. v1 과 v2 TryFrom 이 같은 규칙을 쓴다.
+    // Unknown 은 확인할 규칙이 없으므로 통과시키고, 그룹을 모른다는 에러는 부르는 쪽이 낸다
+    pub fn validate(&self, dto: &RewardDto) -> Result<(), RewardError> {
+        match self {
+            RewardGroup::ASSET => RewardError::check([
+                RewardError::range(dto.min, dto.max),
+                RewardError::unexpected("code", &dto.code),
+                RewardError::unexpected("url", &dto.url),
+            ]),
+            RewardGroup::ITEM => RewardError::check([
+                RewardError::missing("url", &dto.url),
+                RewardError::missing("name", &dto.name),
+                RewardError::unexpected("min", &dto.min),
+                RewardError::unexpected("max", &dto.max),
+            ]),
+            RewardGroup::AVATAR => RewardError::check([
+                RewardError::missing("url", &dto.url),
+                RewardError::unexpected("code", &dto.code),
+                RewardError::unexpected("min", &dto.min),
+                RewardError::unexpected("max", &dto.max),
+                RewardError::unexpected("shelf_life", &dto.shelf_life),
+            ]),
+            RewardGroup::COUPON => RewardError::check([
+                RewardError::missing("code", &dto.code),
+                RewardError::unexpected("url", &dto.url),
+                RewardError::unexpected("min", &dto.min),
+                RewardError::unexpected("max", &dto.max),
+            ]),
+            RewardGroup::QUEST => RewardError::check([
+                RewardError::missing("name", &dto.name),
+                RewardError::unexpected("min", &dto.min),
+                RewardError::unexpected("max", &dto.max),
+            ]),
+            RewardGroup::Unknown(_) => Ok(()),
+        }
+    }
+}
+
+impl From<RewardGroup> for String {
+    fn from(value: RewardGroup) -> Self {
+        match value {
+            RewardGroup::ASSET => String::from("ASSET"),
+            RewardGroup::AVATAR => String::from("AVATAR"),
+            RewardGroup::ITEM => String::from("ITEM"),
+            RewardGroup::COUPON => String::from("COUPON"),
+            RewardGroup::QUEST => String::from("QUEST"),
+            RewardGroup::Unknown(group) => group,
+        }
+    }
+}
+
+// 서버 JSON: { "group": "ASSET", "type": "XP", "isHidden": false, "shelfLife": "2023-07-01T00:00:00Z", ... }
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct RewardDto {
-    pub(crate) group: RewardGroup, // makes item visible within the current crate
+    pub group: RewardGroup,
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
     pub _type: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     pub name: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     pub code: Option<String>,
     pub delta: u32,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     pub min: Option<u32>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     pub max: Option<u32>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     pub url: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub is_hidden: bool,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     pub shelf_life: Option<DateTime<Utc>>,
 }
+
+impl RewardDto {
+    // group 만 정해진 빈 DTO. 나머지는 struct update 문법으로 채운다
+    pub fn empty(group: RewardGroup) -> Self {
+        RewardDto {
+            group,
+            _type: String::new(),
+            name: None,
+            code: None,
+            delta: 0,
+            min: None,
+            max: None,
+            url: None,
+            is_hidden: false,
+            shelf_life: None,
+        }
+    }
+}
+
+// 쿠폰 보상의 사용 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CouponState {
+    #[default]
+    Unredeemed,
+    Redeemed(DateTime<Utc>),
+}
+
+impl CouponState {
+    // 이미 사용한 쿠폰이면 false
+    pub fn redeem(&mut self, at: DateTime<Utc>) -> bool {
+        match self {
+            CouponState::Unredeemed => {
+                *self = CouponState::Redeemed(at);
+                true
+            }
+            CouponState::Redeemed(_) => false,
+        }
+    }
+}
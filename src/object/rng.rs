@@ -0,0 +1,44 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+// 보상 수량을 굴릴 때 쓰는 난수원. 테스트에서는 시드를 고정한 SeededRng 를 넣는다.
+pub trait RewardRng {
+    fn next_u64(&mut self) -> u64;
+
+    // [min, max] 구간의 값. min > max 면 min
+    fn range_inclusive(&mut self, min: u32, max: u32) -> u32 {
+        if min >= max {
+            return min;
+        }
+        let span = u64::from(max - min) + 1;
+        let offset = (u128::from(self.next_u64()) * u128::from(span)) >> 64;
+        min + offset as u32
+    }
+}
+
+// SplitMix64. 같은 시드면 항상 같은 수열이 나온다.
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    // 실행할 때마다 다른 시드 (RandomState 의 랜덤 키를 빌려 쓴다)
+    pub fn from_entropy() -> Self {
+        Self::new(RandomState::new().build_hasher().finish())
+    }
+}
+
+impl RewardRng for SeededRng {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
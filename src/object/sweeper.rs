@@ -0,0 +1,158 @@
+use crate::object::clock::Clock;
+use crate::object::version_1::{
+    Avatar, Coupon, DateTimeExt, Gem, Item, Quest, RewardBundle, RewardObj, RewardVisitor, Unknown,
+};
+use crate::object::version_2::{self, RewardDisplay};
+use chrono::{DateTime, Duration, Utc};
+
+// 유통기한이 있는 보상. v1 RewardObj 와 v2 Reward 를 같은 sweeper 로 돌리기 위한 것
+pub trait Expirable {
+    fn shelf_life(&self) -> Option<DateTime<Utc>>;
+    fn describe(&self) -> String;
+}
+
+// 숨김 보상은 v1, v2 모두 이름 대신 이것으로 보여 준다
+const HIDDEN: &str = "(hidden reward)";
+
+impl Expirable for RewardObj {
+    fn shelf_life(&self) -> Option<DateTime<Utc>> {
+        let mut visitor = ShelfLifeVisitor::default();
+        self.accept(&mut visitor);
+        visitor.0
+    }
+    fn describe(&self) -> String {
+        if self.is_hidden() {
+            return String::from(HIDDEN);
+        }
+        // Unknown 은 표시할 것이 없다. v2 fmt_string 과 같이 빈 문자열
+        self.try_display()
+            .map(|display| display.fmt_string.into())
+            .unwrap_or_default()
+    }
+}
+
+// 보상 종류가 늘면 visit_* 가 빠져서 컴파일 에러로 드러난다
+#[derive(Default)]
+struct ShelfLifeVisitor(Option<DateTime<Utc>>);
+
+impl RewardVisitor for ShelfLifeVisitor {
+    fn visit_unknown(&mut self, _: &Unknown) {
+        self.0 = None;
+    }
+    fn visit_gem(&mut self, gem: &Gem) {
+        self.0 = gem.shelf_life;
+    }
+    fn visit_item(&mut self, item: &Item) {
+        self.0 = item.shelf_life;
+    }
+    // 아바타는 유통기한이 없다
+    fn visit_avatar(&mut self, _: &Avatar) {
+        self.0 = None;
+    }
+    fn visit_coupon(&mut self, coupon: &Coupon) {
+        self.0 = coupon.shelf_life;
+    }
+    fn visit_quest(&mut self, quest: &Quest) {
+        self.0 = quest.shelf_life;
+    }
+    // 묶음은 안쪽에서 가장 먼저 만료되는 보상을 따른다. 안쪽 묶음도 같은 visitor 로 내려간다
+    fn visit_bundle(&mut self, bundle: &RewardBundle) {
+        let earliest = bundle
+            .rewards
+            .iter()
+            .filter_map(|reward| {
+                reward.accept(self);
+                self.0
+            })
+            .min();
+        self.0 = earliest;
+    }
+}
+
+impl Expirable for version_2::Reward {
+    fn shelf_life(&self) -> Option<DateTime<Utc>> {
+        self.get_shelf_life()
+    }
+    fn describe(&self) -> String {
+        if self.is_hidden() {
+            return String::from(HIDDEN);
+        }
+        self.fmt_string()
+    }
+}
+
+pub struct Expired<T> {
+    pub reward: T,
+    pub at: DateTime<Utc>,
+}
+
+pub struct Sweep<T> {
+    pub live: Vec<T>,
+    pub expired: Vec<Expired<T>>,
+}
+
+impl<T: Expirable> Sweep<T> {
+    // "1,000-Gem Pouch Item (EXPIRED AT 2023-07-01 00:00:00)"
+    pub fn report(&self) -> Vec<String> {
+        self.expired
+            .iter()
+            .map(|Expired { reward, at }| {
+                format!("{} (EXPIRED AT {})", reward.describe(), at.label())
+            })
+            .collect()
+    }
+}
+
+// 유통기한 + grace 가 clock 기준으로 지난 보상을 골라낸다
+pub struct Sweeper<'c> {
+    clock: &'c dyn Clock,
+    grace: Duration,
+}
+
+impl<'c> Sweeper<'c> {
+    pub fn new(clock: &'c dyn Clock) -> Self {
+        Self {
+            clock,
+            grace: Duration::zero(),
+        }
+    }
+
+    pub fn with_grace(self, grace: Duration) -> Self {
+        Self { grace, ..self }
+    }
+
+    // 보상이 실제로 치워지는 시각 (유통기한 + grace). 표현할 수 없을 만큼 멀면 None 이고 치우지 않는다
+    fn deadline<T: Expirable>(&self, reward: &T) -> Option<DateTime<Utc>> {
+        reward.shelf_life()?.checked_add_signed(self.grace)
+    }
+
+    pub fn sweep<T: Expirable>(&self, rewards: impl IntoIterator<Item = T>) -> Sweep<T> {
+        let now = self.clock.now();
+        let mut sweep = Sweep {
+            live: Vec::new(),
+            expired: Vec::new(),
+        };
+        for reward in rewards {
+            match (reward.shelf_life(), self.deadline(&reward)) {
+                (Some(at), Some(deadline)) if deadline < now => {
+                    sweep.expired.push(Expired { reward, at })
+                }
+                _ => sweep.live.push(reward),
+            }
+        }
+        sweep
+    }
+
+    // 다음에 sweep 을 돌려야 하는 시각. 앞으로 만료될 보상이 없으면 None
+    pub fn next_expiry<'a, T: Expirable + 'a>(
+        &self,
+        rewards: impl IntoIterator<Item = &'a T>,
+    ) -> Option<DateTime<Utc>> {
+        let now = self.clock.now();
+        rewards
+            .into_iter()
+            .filter_map(|reward| self.deadline(reward))
+            .filter(|deadline| *deadline >= now)
+            .min()
+    }
+}
@@ -1,144 +1,523 @@
-use crate::feature::reward::{RewardDto, RewardGroup};
+use crate::object::clock::{Clock, ManualClock, SystemClock};
+use crate::object::error::{ErrorCollector, ErrorSink, RewardError, StdoutSink};
+use crate::object::inventory::{grant_all, Inventory};
+use crate::object::json;
+use crate::object::loot::{LootDrop, LootTable, PityCounters};
+use crate::object::registry::RewardRegistry;
+use crate::object::reward::{CouponState, RewardDto, RewardGroup};
+use crate::object::rng::{RewardRng, SeededRng};
 use chrono::{DateTime, Utc};
-use std::thread;
+use std::any::{self, Any};
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
 
-enum RewardCast<'a> {
-    Unknown,
-    Gem(&'a Gem),
-    Item(&'a Item),
-}
 #[derive(Debug)]
-struct RewardDisplay {
-    unit_image: Box<str>,
-    image: Box<str>,
-    fmt_string: Box<str>,
+pub struct RewardDisplay {
+    pub unit_image: Box<str>,
+    pub image: Box<str>,
+    pub fmt_string: Box<str>,
 }
 
-trait Reward {
-    fn downcast(&self) -> RewardCast {
-        RewardCast::Unknown
+// Any 를 상속하므로 새 보상 타입도 따로 구현 없이 downcast_ref::<T>() 로 꺼낼 수 있다
+pub trait Reward: Any {
+    // 구체 타입 이름. downcast 실패 메시지용
+    fn type_name(&self) -> &'static str {
+        any::type_name::<Self>()
+    }
+    // 구체 타입에 맞는 visit_* 를 부른다. 기본 구현이 없으므로 새 보상은 반드시 구현해야 한다
+    fn accept(&self, visitor: &mut dyn RewardVisitor);
+    // 숨김 보상은 None. v2 RewardDisplay::is_hidden 과 같은 규칙
+    fn is_hidden(&self) -> bool {
+        let mut visitor = HiddenVisitor::default();
+        self.accept(&mut visitor);
+        visitor.0
     }
     fn try_display(&self) -> Option<RewardDisplay> {
-        None
+        let mut visitor = DisplayVisitor::default();
+        self.accept(&mut visitor);
+        visitor.display.filter(|_| !visitor.hidden)
     }
+    // 범위로 주어진 수량을 확정한다. 수량이 고정된 보상은 아무것도 하지 않는다.
+    // accept 처럼 기본 구현이 없어서 새 보상이 빠뜨리면 컴파일 에러다
+    fn roll(&mut self, rng: &mut dyn RewardRng);
+    // 저장/재전송용 DTO. TryFrom 이 받은 필드를 그대로 돌려준다. DTO 로 나타낼 수 없으면 None
+    fn to_dto(&self) -> Option<RewardDto>;
 }
 
-trait PlayioReward<T> {
-    fn is_hidden(&self) -> bool;
+// 구체 보상마다 메서드가 하나씩 있고 기본 구현은 없다.
+// 보상 종류를 추가하면 아직 처리하지 않은 visitor 가 전부 컴파일 에러로 드러난다
+pub trait RewardVisitor {
+    fn visit_unknown(&mut self, unknown: &Unknown);
+    fn visit_gem(&mut self, gem: &Gem);
+    fn visit_item(&mut self, item: &Item);
+    fn visit_avatar(&mut self, avatar: &Avatar);
+    fn visit_coupon(&mut self, coupon: &Coupon);
+    fn visit_quest(&mut self, quest: &Quest);
+    fn visit_bundle(&mut self, bundle: &RewardBundle);
 }
-struct Unknown;
-struct Gem {
-    delta: u32,
-    min: Option<u32>,
-    max: Option<u32>,
-    is_hidden: bool,
+
+// 여러 보상을 한 번에 주는 묶음 (이벤트 상자 등). 묶음 안에 묶음이 들어갈 수 있다
+pub struct RewardBundle {
+    pub name: String,
+    pub rewards: Vec<RewardObj>,
+    pub is_hidden: bool,
 }
 
-struct Item {
-    url: String,
-    name: String,
-    shelf_life: Option<DateTime<Utc>>,
-    delta: u32,
-    is_hidden: bool,
+pub struct Unknown;
+pub struct Gem {
+    pub currency: String, // RewardDto._type (예: "XP")
+    pub delta: u32,
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+    pub name: Option<String>,
+    pub shelf_life: Option<DateTime<Utc>>,
+    pub is_hidden: bool,
 }
 
-impl PlayioReward<Item> for Item {
-    fn is_hidden(&self) -> bool {
-        self.is_hidden
-    }
-}
-type RewardObj = Box<dyn Reward + Send>;
-trait RewardFactory {
-    fn try_gen(dto: RewardDto) -> thread::Result<RewardObj> {
-        //not recommended. panic catch하는 용법 자체를 피하는게 좋다고 합니다.
-        std::panic::catch_unwind(|| {
-            match dto.group {
-                RewardGroup::ASSET => Box::new(Gem::from(dto)),
-                RewardGroup::ITEM => {
-                    //From & Into trait 은 서로를 자동구현시킴
-                    let item: Item = dto.into();
-                    Box::new(item) as RewardObj
-                }
-                _ => Box::new(Unknown),
-            }
-        })
+pub struct Item {
+    pub item_type: String, // RewardDto._type
+    pub url: String,
+    pub name: String,
+    pub shelf_life: Option<DateTime<Utc>>,
+    pub delta: u32,
+    pub code: Option<String>,
+    pub is_hidden: bool,
+}
+
+// _type 이 코스메틱 id
+pub struct Avatar {
+    pub cosmetic_id: String,
+    pub url: String,
+    pub name: Option<String>,
+    pub delta: u32,
+    pub is_hidden: bool,
+}
+
+pub struct Coupon {
+    pub coupon_type: String, // RewardDto._type
+    pub code: String,
+    pub name: Option<String>,
+    pub state: CouponState,
+    pub shelf_life: Option<DateTime<Utc>>,
+    pub delta: u32,
+    pub is_hidden: bool,
+}
+
+// _type 이 퀘스트 id, delta 가 완료 조건(횟수)
+pub struct Quest {
+    pub quest_id: String,
+    pub name: String,
+    pub required: u32,
+    pub url: Option<String>,
+    pub code: Option<String>,
+    pub shelf_life: Option<DateTime<Utc>>,
+    pub is_hidden: bool,
+}
+
+pub type RewardObj = Box<dyn Reward + Send>;
+
+// downcast 실패. 소유권을 넘긴 downcast 는 원래 보상을 reward 로 돌려준다
+pub struct RewardCastError<R = ()> {
+    pub expected: &'static str,
+    pub actual: &'static str,
+    pub reward: R,
+}
+impl<R> Debug for RewardCastError<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RewardCastError")
+            .field("expected", &self.expected)
+            .field("actual", &self.actual)
+            .finish_non_exhaustive()
     }
-    fn gen(dto: RewardDto) -> RewardObj;
 }
-//stateless 서비스 다형성 예시
-struct RewardFactoryUnsafe;
-impl RewardFactory for RewardFactoryUnsafe {
-    fn gen(dto: RewardDto) -> RewardObj {
-        Self::try_gen(dto).unwrap()
+impl<R> Display for RewardCastError<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {}, found {}", self.expected, self.actual)
     }
 }
-struct RewardFactorySafe;
-impl RewardFactory for RewardFactorySafe {
-    fn gen(dto: RewardDto) -> RewardObj {
-        Self::try_gen(dto).unwrap_or_else(|err| {
-            if let Ok(err_str) = err.downcast::<&str>() {
-                println!("ERROR REPORT: {:?} (RewardFactory::new)", err_str);
-            }
+impl<R> Error for RewardCastError<R> {}
+
+impl dyn Reward + Send {
+    pub fn is<T: Reward>(&self) -> bool {
+        (self as &dyn Any).is::<T>()
+    }
+    pub fn downcast_ref<T: Reward>(&self) -> Result<&T, RewardCastError> {
+        let actual = self.type_name();
+        (self as &dyn Any)
+            .downcast_ref()
+            .ok_or_else(|| cast_error::<T, _>(actual, ()))
+    }
+    pub fn downcast_mut<T: Reward>(&mut self) -> Result<&mut T, RewardCastError> {
+        let actual = self.type_name();
+        (self as &mut dyn Any)
+            .downcast_mut()
+            .ok_or_else(|| cast_error::<T, _>(actual, ()))
+    }
+}
+fn cast_error<T: Reward, R>(actual: &'static str, reward: R) -> RewardCastError<R> {
+    RewardCastError {
+        expected: any::type_name::<T>(),
+        actual,
+        reward,
+    }
+}
+
+// RewardObj::downcast::<T>(). Box 에는 inherent impl 을 달 수 없어서 trait 로 둔다
+pub trait RewardObjExt: Sized {
+    fn downcast<T: Reward>(self) -> Result<Box<T>, RewardCastError<Self>>;
+}
+impl RewardObjExt for RewardObj {
+    fn downcast<T: Reward>(self) -> Result<Box<T>, RewardCastError<Self>> {
+        if !self.is::<T>() {
+            return Err(cast_error::<T, _>(self.type_name(), self));
+        }
+        let any: Box<dyn Any + Send> = self;
+        Ok(any.downcast().unwrap_or_else(|_| unreachable!()))
+    }
+}
+pub trait RewardFactory {
+    fn registry(&self) -> &RewardRegistry;
+    // 만든 보상의 유통기한은 이 시계로 확인한다 (ShelfLife::expired_at)
+    fn clock(&self) -> &dyn Clock;
+    fn try_gen(&self, dto: RewardDto) -> Result<RewardObj, RewardError> {
+        self.registry().build(dto)
+    }
+    fn gen(&self, dto: RewardDto) -> RewardObj;
+}
+pub struct RewardFactoryUnsafe<C: Clock = SystemClock> {
+    pub registry: RewardRegistry,
+    pub clock: C,
+}
+impl Default for RewardFactoryUnsafe {
+    fn default() -> Self {
+        Self {
+            registry: RewardRegistry::builtin(),
+            clock: SystemClock,
+        }
+    }
+}
+impl<C: Clock> RewardFactoryUnsafe<C> {
+    pub fn with_clock<T: Clock>(self, clock: T) -> RewardFactoryUnsafe<T> {
+        RewardFactoryUnsafe {
+            registry: self.registry,
+            clock,
+        }
+    }
+}
+impl<C: Clock> RewardFactory for RewardFactoryUnsafe<C> {
+    fn registry(&self) -> &RewardRegistry {
+        &self.registry
+    }
+    fn clock(&self) -> &dyn Clock {
+        &self.clock
+    }
+    fn gen(&self, dto: RewardDto) -> RewardObj {
+        self.try_gen(dto)
+            .unwrap_or_else(|err| panic!("invalid reward: {err}"))
+    }
+}
+//실패하면 Unknown 으로 대체하고, 이유는 주입받은 sink 에 남긴다
+pub struct RewardFactorySafe<S: ErrorSink = StdoutSink, C: Clock = SystemClock> {
+    pub registry: RewardRegistry,
+    pub sink: S,
+    pub clock: C,
+}
+impl<S: ErrorSink> RewardFactorySafe<S> {
+    pub fn new(sink: S) -> Self {
+        Self::with_registry(RewardRegistry::builtin(), sink)
+    }
+    pub fn with_registry(registry: RewardRegistry, sink: S) -> Self {
+        Self {
+            registry,
+            sink,
+            clock: SystemClock,
+        }
+    }
+}
+impl<S: ErrorSink, C: Clock> RewardFactorySafe<S, C> {
+    pub fn with_clock<T: Clock>(self, clock: T) -> RewardFactorySafe<S, T> {
+        RewardFactorySafe {
+            registry: self.registry,
+            sink: self.sink,
+            clock,
+        }
+    }
+}
+impl Default for RewardFactorySafe {
+    fn default() -> Self {
+        Self::new(StdoutSink)
+    }
+}
+impl<S: ErrorSink, C: Clock> RewardFactory for RewardFactorySafe<S, C> {
+    fn registry(&self) -> &RewardRegistry {
+        &self.registry
+    }
+    fn clock(&self) -> &dyn Clock {
+        &self.clock
+    }
+    fn gen(&self, dto: RewardDto) -> RewardObj {
+        self.try_gen(dto).unwrap_or_else(|err| {
+            self.sink.report(&err);
             Box::new(Unknown)
         })
     }
 }
-fn gen_reward<T: RewardFactory>(dto: RewardDto) -> RewardObj {
-    T::gen(dto)
+pub fn gen_reward(factory: &impl RewardFactory, dto: RewardDto) -> RewardObj {
+    factory.gen(dto)
 }
-impl From<RewardDto> for Gem {
-    fn from(value: RewardDto) -> Self {
-        Gem {
+impl TryFrom<RewardDto> for Gem {
+    type Error = RewardError;
+    fn try_from(value: RewardDto) -> Result<Self, Self::Error> {
+        RewardGroup::ASSET.validate(&value)?;
+        Ok(Gem {
+            currency: value._type,
             delta: value.delta,
             min: value.min,
             max: value.max,
+            name: value.name,
+            shelf_life: value.shelf_life,
             is_hidden: value.is_hidden,
-        }
+        })
     }
 }
 
-impl From<RewardDto> for Item {
-    fn from(value: RewardDto) -> Self {
-        Self {
-            url: value.url.unwrap(),
-            name: value.name.unwrap(),
+impl TryFrom<RewardDto> for Item {
+    type Error = RewardError;
+    fn try_from(value: RewardDto) -> Result<Self, Self::Error> {
+        RewardGroup::ITEM.validate(&value)?;
+        Ok(Self {
+            item_type: value._type,
+            url: value.url.unwrap_or_default(),
+            name: value.name.unwrap_or_default(),
             delta: value.delta,
             shelf_life: value.shelf_life,
+            code: value.code,
             is_hidden: value.is_hidden,
-        }
+        })
     }
 }
 
-impl Reward for Unknown {}
+impl TryFrom<RewardDto> for Avatar {
+    type Error = RewardError;
+    fn try_from(value: RewardDto) -> Result<Self, Self::Error> {
+        RewardGroup::AVATAR.validate(&value)?;
+        Ok(Avatar {
+            cosmetic_id: value._type,
+            url: value.url.unwrap_or_default(),
+            name: value.name,
+            delta: value.delta,
+            is_hidden: value.is_hidden,
+        })
+    }
+}
+
+impl TryFrom<RewardDto> for Coupon {
+    type Error = RewardError;
+    fn try_from(value: RewardDto) -> Result<Self, Self::Error> {
+        RewardGroup::COUPON.validate(&value)?;
+        Ok(Coupon {
+            coupon_type: value._type,
+            code: value.code.unwrap_or_default(),
+            name: value.name,
+            state: CouponState::Unredeemed,
+            shelf_life: value.shelf_life,
+            delta: value.delta,
+            is_hidden: value.is_hidden,
+        })
+    }
+}
+
+impl TryFrom<RewardDto> for Quest {
+    type Error = RewardError;
+    fn try_from(value: RewardDto) -> Result<Self, Self::Error> {
+        RewardGroup::QUEST.validate(&value)?;
+        Ok(Quest {
+            quest_id: value._type,
+            name: value.name.unwrap_or_default(),
+            required: value.delta,
+            url: value.url,
+            code: value.code,
+            shelf_life: value.shelf_life,
+            is_hidden: value.is_hidden,
+        })
+    }
+}
+
+impl Reward for Unknown {
+    fn accept(&self, visitor: &mut dyn RewardVisitor) {
+        visitor.visit_unknown(self)
+    }
+    fn roll(&mut self, _rng: &mut dyn RewardRng) {}
+    fn to_dto(&self) -> Option<RewardDto> {
+        None
+    }
+}
 impl Reward for Item {
-    fn downcast(&self) -> RewardCast {
-        RewardCast::Item(self)
+    fn accept(&self, visitor: &mut dyn RewardVisitor) {
+        visitor.visit_item(self)
     }
-    fn try_display(&self) -> Option<RewardDisplay> {
-        Some(self.display())
+    fn roll(&mut self, _rng: &mut dyn RewardRng) {}
+    fn to_dto(&self) -> Option<RewardDto> {
+        Some(RewardDto {
+            _type: self.item_type.clone(),
+            name: Some(self.name.clone()),
+            url: Some(self.url.clone()),
+            code: self.code.clone(),
+            delta: self.delta,
+            is_hidden: self.is_hidden,
+            shelf_life: self.shelf_life,
+            ..RewardDto::empty(RewardGroup::ITEM)
+        })
     }
 }
 impl Reward for Gem {
-    fn downcast(&self) -> RewardCast {
-        RewardCast::Gem(self)
+    fn accept(&self, visitor: &mut dyn RewardVisitor) {
+        visitor.visit_gem(self)
     }
-    fn try_display(&self) -> Option<RewardDisplay> {
-        if self.is_hidden {
-            None
-        } else {
-            Some(self.display())
+    fn roll(&mut self, rng: &mut dyn RewardRng) {
+        if let Some((min, max)) = self.range() {
+            self.delta = rng.range_inclusive(min, max);
+            self.min = None;
+            self.max = None;
         }
     }
+    fn to_dto(&self) -> Option<RewardDto> {
+        Some(RewardDto {
+            _type: self.currency.clone(),
+            delta: self.delta,
+            min: self.min,
+            max: self.max,
+            name: self.name.clone(),
+            shelf_life: self.shelf_life,
+            is_hidden: self.is_hidden,
+            ..RewardDto::empty(RewardGroup::ASSET)
+        })
+    }
+}
+impl Reward for Avatar {
+    fn accept(&self, visitor: &mut dyn RewardVisitor) {
+        visitor.visit_avatar(self)
+    }
+    fn roll(&mut self, _rng: &mut dyn RewardRng) {}
+    fn to_dto(&self) -> Option<RewardDto> {
+        Some(RewardDto {
+            _type: self.cosmetic_id.clone(),
+            url: Some(self.url.clone()),
+            name: self.name.clone(),
+            delta: self.delta,
+            is_hidden: self.is_hidden,
+            ..RewardDto::empty(RewardGroup::AVATAR)
+        })
+    }
+}
+impl Reward for Coupon {
+    fn accept(&self, visitor: &mut dyn RewardVisitor) {
+        visitor.visit_coupon(self)
+    }
+    fn roll(&mut self, _rng: &mut dyn RewardRng) {}
+    fn to_dto(&self) -> Option<RewardDto> {
+        Some(RewardDto {
+            _type: self.coupon_type.clone(),
+            name: self.name.clone(),
+            code: Some(self.code.clone()),
+            delta: self.delta,
+            is_hidden: self.is_hidden,
+            shelf_life: self.shelf_life,
+            ..RewardDto::empty(RewardGroup::COUPON)
+        })
+    }
 }
-trait DisplayableReward: Reward {
+impl Reward for Quest {
+    fn accept(&self, visitor: &mut dyn RewardVisitor) {
+        visitor.visit_quest(self)
+    }
+    fn roll(&mut self, _rng: &mut dyn RewardRng) {}
+    fn to_dto(&self) -> Option<RewardDto> {
+        Some(RewardDto {
+            _type: self.quest_id.clone(),
+            name: Some(self.name.clone()),
+            delta: self.required,
+            url: self.url.clone(),
+            code: self.code.clone(),
+            shelf_life: self.shelf_life,
+            is_hidden: self.is_hidden,
+            ..RewardDto::empty(RewardGroup::QUEST)
+        })
+    }
+}
+// try_display/is_hidden 의 본체. Unknown 은 보여 줄 것이 없다
+#[derive(Default)]
+struct DisplayVisitor {
+    display: Option<RewardDisplay>,
+    hidden: bool,
+}
+impl DisplayVisitor {
+    fn show<R: DisplayableReward>(&mut self, reward: &R, is_hidden: bool) {
+        self.display = Some(reward.display());
+        self.hidden = is_hidden;
+    }
+}
+impl RewardVisitor for DisplayVisitor {
+    fn visit_unknown(&mut self, _: &Unknown) {
+        self.display = None;
+        self.hidden = false;
+    }
+    fn visit_gem(&mut self, gem: &Gem) {
+        self.show(gem, gem.is_hidden)
+    }
+    fn visit_item(&mut self, item: &Item) {
+        self.show(item, item.is_hidden)
+    }
+    fn visit_avatar(&mut self, avatar: &Avatar) {
+        self.show(avatar, avatar.is_hidden)
+    }
+    fn visit_coupon(&mut self, coupon: &Coupon) {
+        self.show(coupon, coupon.is_hidden)
+    }
+    fn visit_quest(&mut self, quest: &Quest) {
+        self.show(quest, quest.is_hidden)
+    }
+    fn visit_bundle(&mut self, bundle: &RewardBundle) {
+        self.show(bundle, bundle.is_hidden)
+    }
+}
+
+// is_hidden 필드만 읽는다. 표시 문자열은 만들지 않는다
+#[derive(Default)]
+struct HiddenVisitor(bool);
+impl RewardVisitor for HiddenVisitor {
+    fn visit_unknown(&mut self, _: &Unknown) {
+        self.0 = false;
+    }
+    fn visit_gem(&mut self, gem: &Gem) {
+        self.0 = gem.is_hidden;
+    }
+    fn visit_item(&mut self, item: &Item) {
+        self.0 = item.is_hidden;
+    }
+    fn visit_avatar(&mut self, avatar: &Avatar) {
+        self.0 = avatar.is_hidden;
+    }
+    fn visit_coupon(&mut self, coupon: &Coupon) {
+        self.0 = coupon.is_hidden;
+    }
+    fn visit_quest(&mut self, quest: &Quest) {
+        self.0 = quest.is_hidden;
+    }
+    fn visit_bundle(&mut self, bundle: &RewardBundle) {
+        self.0 = bundle.is_hidden;
+    }
+}
+
+pub trait DisplayableReward: Reward {
     fn display(&self) -> RewardDisplay;
 }
 impl DisplayableReward for Item {
     fn display(&self) -> RewardDisplay {
         RewardDisplay {
-            unit_image: Box::from(""),
-            image: Box::from(""),
-            fmt_string: Box::from(format!("Item({})", self.name)),
+            unit_image: Box::from("Item Image"),
+            image: Box::from(self.url.as_str()),
+            fmt_string: Box::from(format!("{} Item", self.name)),
         }
     }
 }
@@ -148,38 +527,185 @@ impl DisplayableReward for Gem {
         RewardDisplay {
             unit_image: Box::from("Gem Image"),
             image: Box::from("Gem Image"),
-            fmt_string: Box::from(format!("{} Gems", self.delta)),
+            fmt_string: match self.range() {
+                Some((min, max)) => Box::from(format!("{min}–{max} Gems")),
+                None => Box::from(format!("{} Gems", self.delta)),
+            },
         }
     }
 }
 
-struct RewardCastError;
-impl Item {
-    fn try_downcast_from(reward: &RewardObj) -> Result<&Self, RewardCastError> {
-        if let RewardCast::Item(item) = reward.downcast() {
-            Ok(item)
-        } else {
-            Err(RewardCastError)
+impl DisplayableReward for Avatar {
+    fn display(&self) -> RewardDisplay {
+        RewardDisplay {
+            unit_image: Box::from("Avatar Image"),
+            image: Box::from(self.url.as_str()),
+            fmt_string: Box::from(format!("{} Avatar", self.cosmetic_id)),
+        }
+    }
+}
+
+impl DisplayableReward for Coupon {
+    fn display(&self) -> RewardDisplay {
+        let label = self.name.as_deref().unwrap_or(&self.code);
+        let fmt_string = match self.state {
+            CouponState::Unredeemed => format!("{label} Coupon"),
+            CouponState::Redeemed(_) => format!("{label} Coupon (redeemed)"),
+        };
+        RewardDisplay {
+            unit_image: Box::from("Coupon Image"),
+            image: Box::from("Coupon Image"),
+            fmt_string: Box::from(fmt_string),
+        }
+    }
+}
+
+impl Reward for RewardBundle {
+    fn accept(&self, visitor: &mut dyn RewardVisitor) {
+        visitor.visit_bundle(self)
+    }
+    fn roll(&mut self, rng: &mut dyn RewardRng) {
+        for reward in &mut self.rewards {
+            reward.roll(rng);
+        }
+    }
+    fn to_dto(&self) -> Option<RewardDto> {
+        None
+    }
+}
+impl DisplayableReward for Quest {
+    fn display(&self) -> RewardDisplay {
+        RewardDisplay {
+            unit_image: Box::from("Quest Image"),
+            image: Box::from("Quest Image"),
+            fmt_string: Box::from(format!("{} Quest x{}", self.name, self.required)),
+        }
+    }
+}
+
+// "Spring Chest (3000 Gems + FRAME_GOLD Avatar)". 숨김 보상은 빠진다
+impl DisplayableReward for RewardBundle {
+    fn display(&self) -> RewardDisplay {
+        let children: Vec<_> = self
+            .rewards
+            .iter()
+            .filter_map(|reward| reward.try_display())
+            .map(|display| display.fmt_string)
+            .collect();
+        let fmt_string = match children.is_empty() {
+            true => self.name.clone(),
+            false => format!("{} ({})", self.name, children.join(" + ")),
+        };
+        RewardDisplay {
+            unit_image: Box::from("Bundle Image"),
+            image: Box::from("Bundle Image"),
+            fmt_string: Box::from(fmt_string),
+        }
+    }
+}
+
+impl RewardBundle {
+    pub fn new(name: impl Into<String>, rewards: Vec<RewardObj>) -> Self {
+        Self {
+            name: name.into(),
+            rewards,
+            is_hidden: false,
+        }
+    }
+
+    // 안쪽 묶음을 모두 풀어 낱개 보상만 남긴다.
+    // 숨김 묶음은 풀면 숨김 여부를 잃으므로 묶음째 둔다
+    pub fn flatten(self) -> Vec<RewardObj> {
+        let mut flat: Vec<RewardObj> = Vec::new();
+        for reward in self.rewards {
+            match reward.downcast::<RewardBundle>() {
+                Ok(bundle) if bundle.is_hidden => flat.push(bundle),
+                Ok(bundle) => flat.extend(bundle.flatten()),
+                Err(err) => flat.push(err.reward),
+            }
+        }
+        flat
+    }
+
+    // 묶음은 DTO 하나로 나타낼 수 없다 (to_dto 는 None). 안쪽 보상의 DTO 를 순서대로 모은다.
+    // 숨김 묶음 안의 보상은 is_hidden 을 켜서 내보낸다
+    pub fn to_dtos(&self) -> Vec<RewardDto> {
+        let mut dtos = Vec::new();
+        for reward in &self.rewards {
+            match reward.downcast_ref::<RewardBundle>() {
+                Ok(bundle) => dtos.extend(bundle.to_dtos()),
+                Err(_) => dtos.extend(reward.to_dto()),
+            }
+        }
+        for dto in &mut dtos {
+            dto.is_hidden |= self.is_hidden;
+        }
+        dtos
+    }
+
+    // flatten 한 뒤 같은 재화의 Gem 을 하나로 합친다.
+    // 아직 굴리지 않은 범위 Gem, 숨김 여부가 다른 Gem, 숨김 묶음 안의 Gem 은 따로 둔다
+    pub fn merge(self) -> Self {
+        let name = self.name.clone();
+        let is_hidden = self.is_hidden;
+        let mut rewards: Vec<RewardObj> = Vec::new();
+        for reward in self.flatten() {
+            let gem = match reward.downcast::<Gem>() {
+                Ok(gem) => gem,
+                Err(err) => {
+                    rewards.push(err.reward);
+                    continue;
+                }
+            };
+            // 합치면 넘치는 경우도 따로 둔다
+            let target = rewards
+                .iter_mut()
+                .filter_map(|reward| reward.downcast_mut::<Gem>().ok())
+                .find(|other| other.merges_with(&gem))
+                .and_then(|other| Some((other.delta.checked_add(gem.delta)?, other)));
+            match target {
+                Some((sum, other)) => other.delta = sum,
+                None => rewards.push(gem),
+            }
+        }
+        Self {
+            name,
+            rewards,
+            is_hidden,
         }
     }
 }
+
 impl Gem {
-    fn shine(&self) {
+    fn merges_with(&self, other: &Gem) -> bool {
+        self.currency == other.currency
+            && self.name == other.name
+            && self.shelf_life == other.shelf_life
+            && self.is_hidden == other.is_hidden
+            && self.range().is_none()
+            && other.range().is_none()
+    }
+    // 아직 굴리지 않은 수량 범위
+    pub fn range(&self) -> Option<(u32, u32)> {
+        self.min.zip(self.max)
+    }
+    pub fn shine(&self) {
         let power = self.delta;
         println!("bling bling {power}");
     }
-    fn try_downcast_from(reward: &RewardObj) -> Result<&Self, RewardCastError> {
-        if let RewardCast::Gem(gem) = reward.downcast() {
-            Ok(gem)
-        } else {
-            Err(RewardCastError)
-        }
+}
+impl Coupon {
+    pub fn redeem(&mut self, clock: &dyn Clock) -> bool {
+        self.state.redeem(clock.now())
     }
 }
 pub fn test() {
+    // 데모 시각을 고정해 두고 필요할 때 앞으로 돌린다
+    let clock = ManualClock::new(SystemClock.now());
     assert_eq!(
         DateTime::<Utc>::default(), //1970.1.1
-        Utc::now()
+        clock
+            .now()
             .checked_sub_days(chrono::Days::new(18446744073709551615))
             .unwrap_or_default()
     );
@@ -207,54 +733,222 @@ pub fn test() {
         min: None,
         max: None,
         is_hidden: false,
-        shelf_life: Utc::now()
+        shelf_life: clock
+            .now()
             .checked_sub_days(chrono::Days::new(1))
             // .checked_sub_signed(chrono::Duration::days(1)) //dst로인한 버그가능
             .or(Some(DateTime::default())),
     };
     let dto_invalid = dto2.clone();
-    let gen_reward = gen_reward::<RewardFactoryUnsafe>;
-    let reward1 = gen_reward(dto);
+    let factory = RewardFactoryUnsafe::default().with_clock(&clock);
+    let reward1 = gen_reward(&factory, dto);
     if let Some(display) = reward1.try_display() {
         println!("reward1: {:?}", display);
     }
-    if let Ok(gem) = Gem::try_downcast_from(&reward1) {
+    if let Ok(gem) = reward1.downcast_ref::<Gem>() {
         print!("it shines!: ");
         gem.shine();
     }
 
-    print!("\n");
-    let reward2 = gen_reward(dto2);
+    println!();
+    let reward2 = gen_reward(&factory, dto2);
     if let Some(display) = reward2.try_display() {
         println!("reward2: {:?}", display);
     }
     if let Ok(Item {
         name, shelf_life, ..
-    }) = Item::try_downcast_from(&reward2)
+    }) = reward2.downcast_ref::<Item>()
     {
         print!("Item {name}");
-        if let Some(expr) = shelf_life.expired_at() {
+        if let Some(expr) = shelf_life.expired_at(factory.clock()) {
             print!(" (EXPIRED AT {})", expr.label());
         }
         println!();
     }
-    print!("\n");
+    println!();
+    let dto_star = RewardDto {
+        _type: String::from("STAR"),
+        delta: 5,
+        ..RewardDto::empty(RewardGroup::ASSET)
+    };
+    //게임 모듈이 version_1 을 건드리지 않고 보상 종류를 추가하는 예시
+    let mut registry = RewardRegistry::builtin();
+    registry.register(RewardGroup::ASSET, "STAR", |dto| {
+        Ok(Box::new(Gem {
+            delta: dto.delta * 10,
+            ..Gem::try_from(dto)?
+        }))
+    });
+    let star_factory = RewardFactorySafe::with_registry(registry, StdoutSink);
+    if let Some(display) = star_factory.gen(dto_star.clone()).try_display() {
+        println!("reward3: {:?}", display);
+    }
+    let dto_avatar = RewardDto {
+        group: RewardGroup::AVATAR,
+        _type: String::from("FRAME_GOLD"),
+        url: Some(String::from(
+            "https://static.playio.club/__asset/frame_gold.png",
+        )),
+        ..dto_star.clone()
+    };
+    let reward4 = gen_reward(&factory, dto_avatar.clone());
+    if let Ok(avatar) = reward4.downcast_ref::<Avatar>() {
+        println!("reward4: Avatar {} ({})", avatar.cosmetic_id, avatar.url);
+    }
+    println!();
+    let dto_ranged = RewardDto {
+        min: Some(100),
+        max: Some(500),
+        ..dto_star.clone()
+    };
+    let mut reward5 = gen_reward(
+        &factory,
+        RewardDto {
+            _type: String::from("XP"),
+            ..dto_ranged
+        },
+    );
+    if let Some(display) = reward5.try_display() {
+        println!("reward5: {:?}", display);
+    }
+    reward5.roll(&mut SeededRng::new(42));
+    if let Some(display) = reward5.try_display() {
+        println!("reward5 rolled: {:?}", display);
+    }
+    println!();
+    let mut inventory = Inventory::new();
+    let rewards = [reward1, reward2, reward4, reward5];
+    match grant_all(&mut inventory, &rewards) {
+        Ok(receipt) => println!("granted: {:?}", receipt.granted),
+        Err(err) => println!("grant failed: {err}"),
+    }
+    println!("XP balance: {}", inventory.balance("XP"));
+    println!("json: {}", json::to_json_array(&rewards));
+    println!();
+    // 묶음 안에 묶음. merge 하면 XP Gem 두 개가 하나로 합쳐진다
+    let xp = |delta| Gem {
+        currency: String::from("XP"),
+        delta,
+        min: None,
+        max: None,
+        name: None,
+        shelf_life: None,
+        is_hidden: false,
+    };
+    let star_pack = RewardBundle::new(
+        "Star Pack",
+        vec![star_factory.gen(dto_star.clone()), Box::new(xp(500))],
+    );
+    let chest = RewardBundle::new(
+        "Spring Chest",
+        vec![Box::new(xp(1000)), Box::new(star_pack)],
+    );
+    if let Some(display) = chest.try_display() {
+        println!("chest: {}", display.fmt_string);
+    }
+    let chest: RewardObj = Box::new(chest.merge());
+    if let Some(display) = chest.try_display() {
+        println!("chest merged: {}", display.fmt_string);
+    }
+    match grant_all(&mut inventory, &[chest]) {
+        Ok(receipt) => println!("granted: {:?}", receipt.granted),
+        Err(err) => println!("grant failed: {err}"),
+    }
+    println!("XP balance: {}", inventory.balance("XP"));
+    println!();
+    // 상자: XP 는 항상, 나머지는 weight 대로. STAR 묶음 테이블은 3번째 굴림까지 한 번은 나온다
+    let loot = match daily_chest(&dto_star, dto_avatar, &dto_invalid) {
+        Ok(loot) => loot,
+        Err(err) => return println!("loot table rejected: {err}"),
+    };
+    match loot.drop_rates() {
+        Ok(rates) => {
+            for rate in rates {
+                println!(
+                    "drop rate: {} {} x{:?} = {} ({:.1}%)",
+                    rate.path.join(" > "),
+                    rate.template._type,
+                    rate.quantity,
+                    rate.chance,
+                    rate.chance.as_f64() * 100.0
+                );
+            }
+        }
+        Err(err) => println!("drop rates unavailable: {err}"),
+    }
+    let mut rng = SeededRng::new(7);
+    let mut pity = PityCounters::new();
+    for _ in 0..3 {
+        let drops: Vec<String> = loot
+            .roll(&mut rng, &mut pity)
+            .into_iter()
+            .filter_map(|dto| star_factory.gen(dto).try_display())
+            .map(|display| display.fmt_string.into())
+            .collect();
+        println!("loot: {}", drops.join(" + "));
+    }
+    println!();
     factory_glitched(dto_invalid);
 }
-trait ShelfLife {
-    fn expired_at(&self) -> Option<&DateTime<Utc>>;
+fn daily_chest(
+    dto_star: &RewardDto,
+    dto_avatar: RewardDto,
+    dto_invalid: &RewardDto,
+) -> Result<LootTable, RewardError> {
+    let star_table = LootTable::new("star_pack", "Star Pack")
+        .with_entry(1, LootDrop::reward(dto_star.clone(), 10..=20)?)
+        .with_entry(1, LootDrop::reward(dto_avatar, 1..=1)?);
+    LootTable::new("daily_chest", "Daily Chest")
+        .with_guaranteed(LootDrop::reward(
+            RewardDto {
+                _type: String::from("XP"),
+                ..dto_star.clone()
+            },
+            100..=300,
+        )?)
+        .with_entry(6, LootDrop::Nothing)
+        .with_entry(
+            3,
+            LootDrop::reward(
+                RewardDto {
+                    shelf_life: None,
+                    ..dto_invalid.clone()
+                },
+                1..=2,
+            )?,
+        )
+        .with_entry(1, LootDrop::table(star_table))
+        .with_pity(2, 3)
+}
+pub trait ShelfLife {
+    fn expired_at(&self, clock: &dyn Clock) -> Option<&DateTime<Utc>>;
 }
 impl ShelfLife for Option<DateTime<Utc>> {
-    fn expired_at(&self) -> Option<&DateTime<Utc>> {
-        self.as_ref().filter(|&d| d < &Utc::now())
+    fn expired_at(&self, clock: &dyn Clock) -> Option<&DateTime<Utc>> {
+        self.as_ref().filter(|&d| d < &clock.now())
     }
 }
 impl ShelfLife for Item {
-    fn expired_at(&self) -> Option<&DateTime<Utc>> {
-        self.shelf_life.expired_at()
+    fn expired_at(&self, clock: &dyn Clock) -> Option<&DateTime<Utc>> {
+        self.shelf_life.expired_at(clock)
     }
 }
-trait DateTimeExt {
+impl ShelfLife for Coupon {
+    fn expired_at(&self, clock: &dyn Clock) -> Option<&DateTime<Utc>> {
+        self.shelf_life.expired_at(clock)
+    }
+}
+impl ShelfLife for Gem {
+    fn expired_at(&self, clock: &dyn Clock) -> Option<&DateTime<Utc>> {
+        self.shelf_life.expired_at(clock)
+    }
+}
+impl ShelfLife for Quest {
+    fn expired_at(&self, clock: &dyn Clock) -> Option<&DateTime<Utc>> {
+        self.shelf_life.expired_at(clock)
+    }
+}
+pub trait DateTimeExt {
     fn label(&self) -> String;
 }
 impl DateTimeExt for DateTime<Utc> {
@@ -273,22 +967,26 @@ fn factory_glitched(dto: RewardDto) {
         shelf_life: None,
         ..dto
     };
-    let reward_err = gen_reward::<RewardFactorySafe>(dto.clone());
+    let collector = ErrorCollector::default();
+    let reward_err = gen_reward(&RewardFactorySafe::new(&collector), dto.clone());
+    for err in collector.take() {
+        println!("ERROR REPORT: {err} (RewardFactory::gen)");
+    }
     let cb: Box<dyn FnOnce() -> Result<(), RewardCastError>> = match dto.group {
         RewardGroup::ASSET => Box::new(move || {
-            let gem = Gem::try_downcast_from(&reward_err)?;
+            let gem = reward_err.downcast_ref::<Gem>()?;
             print!("it shines!: ");
             gem.shine();
             Ok(())
         }),
         RewardGroup::ITEM => Box::new(move || {
-            let Item { name, .. } = Item::try_downcast_from(&reward_err)?;
+            let Item { name, .. } = reward_err.downcast_ref::<Item>()?;
             println!("Item name: {name}");
             Ok(())
         }),
         _ => Box::new(|| Ok(())),
     };
-    if let Err(_) = cb() {
-        println!("Invalid Reward data: reward_err");
+    if let Err(err) = cb() {
+        println!("Invalid Reward data: reward_err ({err})");
     }
 }
@@ -1,61 +1,364 @@
-use crate::feature::reward::{RewardDto, RewardGroup};
+use crate::object::clock::{Clock, ManualClock, SystemClock};
+use crate::object::error::RewardError;
+use crate::object::reward::{CouponState, RewardDto, RewardGroup};
+use crate::object::rng::{RewardRng, SeededRng};
+use crate::object::sweeper::Sweeper;
+use crate::object::version_1::DateTimeExt;
 use chrono::{DateTime, Utc};
 // enumc으로 Reward 정의하기?
-enum Reward {
+pub enum Reward {
     Item {
+        item_type: String,
         url: String,
         name: String,
         shelf_life: Option<DateTime<Utc>>,
         delta: u32,
+        code: Option<String>,
         is_hidden: bool,
     },
     Gem {
+        currency: String, // RewardDto._type (예: "XP")
         delta: u32,
         min: Option<u32>,
         max: Option<u32>,
+        name: Option<String>,
+        shelf_life: Option<DateTime<Utc>>,
+        is_hidden: bool,
+    },
+    Avatar {
+        cosmetic_id: String,
+        url: String,
+        name: Option<String>,
+        delta: u32,
+        is_hidden: bool,
+    },
+    Coupon {
+        coupon_type: String,
+        code: String,
+        name: Option<String>,
+        state: CouponState,
+        shelf_life: Option<DateTime<Utc>>,
+        delta: u32,
+        is_hidden: bool,
+    },
+    Quest {
+        quest_id: String,
+        name: String,
+        required: u32,
+        url: Option<String>,
+        code: Option<String>,
+        shelf_life: Option<DateTime<Utc>>,
+        is_hidden: bool,
+    },
+    // 여러 보상을 한 번에 주는 묶음. 안에 묶음이 들어갈 수 있다
+    Bundle {
+        name: String,
+        rewards: Vec<Reward>,
         is_hidden: bool,
     },
     Unknown,
 }
 
-impl From<RewardDto> for Reward {
-    fn from(value: RewardDto) -> Self {
+impl TryFrom<RewardDto> for Reward {
+    type Error = RewardError;
+    fn try_from(value: RewardDto) -> Result<Self, Self::Error> {
+        value.group.validate(&value)?;
         match value.group {
-            RewardGroup::ITEM => Reward::Item {
-                url: value.url.unwrap(),
-                name: value.name.unwrap(),
+            RewardGroup::ITEM => Ok(Reward::Item {
+                item_type: value._type,
+                url: value.url.unwrap_or_default(),
+                name: value.name.unwrap_or_default(),
                 shelf_life: value.shelf_life,
                 delta: value.delta,
+                code: value.code,
                 is_hidden: value.is_hidden,
-            },
-            RewardGroup::ASSET => match value._type == "XP" {
-                true => Reward::Gem {
-                    delta: value.delta,
-                    min: value.min,
-                    max: value.max,
-                    is_hidden: value.is_hidden,
+            }),
+            RewardGroup::ASSET => Ok(Reward::Gem {
+                currency: value._type,
+                delta: value.delta,
+                min: value.min,
+                max: value.max,
+                name: value.name,
+                shelf_life: value.shelf_life,
+                is_hidden: value.is_hidden,
+            }),
+            RewardGroup::AVATAR => Ok(Reward::Avatar {
+                cosmetic_id: value._type,
+                url: value.url.unwrap_or_default(),
+                name: value.name,
+                delta: value.delta,
+                is_hidden: value.is_hidden,
+            }),
+            RewardGroup::COUPON => Ok(Reward::Coupon {
+                coupon_type: value._type,
+                code: value.code.unwrap_or_default(),
+                name: value.name,
+                state: CouponState::Unredeemed,
+                shelf_life: value.shelf_life,
+                delta: value.delta,
+                is_hidden: value.is_hidden,
+            }),
+            RewardGroup::QUEST => Ok(Reward::Quest {
+                quest_id: value._type,
+                name: value.name.unwrap_or_default(),
+                required: value.delta,
+                url: value.url,
+                code: value.code,
+                shelf_life: value.shelf_life,
+                is_hidden: value.is_hidden,
+            }),
+            RewardGroup::Unknown(group) => Err(RewardError::UnknownGroup(group)),
+        }
+    }
+}
+
+impl Reward {
+    pub fn bundle(name: impl Into<String>, rewards: Vec<Reward>) -> Self {
+        Reward::Bundle {
+            name: name.into(),
+            rewards,
+            is_hidden: false,
+        }
+    }
+
+    // Gem 의 min/max 범위를 rng 로 굴려 delta 를 확정한다. 묶음은 안쪽까지
+    pub fn roll(&mut self, rng: &mut dyn RewardRng) {
+        match self {
+            Reward::Gem {
+                delta, min, max, ..
+            } => {
+                if let (Some(lo), Some(hi)) = (*min, *max) {
+                    *delta = rng.range_inclusive(lo, hi);
+                    *min = None;
+                    *max = None;
+                }
+            }
+            Reward::Bundle { rewards, .. } => {
+                for reward in rewards {
+                    reward.roll(rng);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // 묶음을 모두 풀어 낱개 보상만 남긴다. 묶음이 아니면 자기 자신 하나.
+    // 안쪽의 숨김 묶음은 풀면 숨김 여부를 잃으므로 묶음째 둔다
+    pub fn flatten(self) -> Vec<Reward> {
+        match self {
+            Reward::Bundle { rewards, .. } => rewards
+                .into_iter()
+                .flat_map(|reward| match reward {
+                    Reward::Bundle {
+                        is_hidden: true, ..
+                    } => vec![reward],
+                    reward => reward.flatten(),
+                })
+                .collect(),
+            reward => vec![reward],
+        }
+    }
+
+    // to_dto 와 같지만 묶음은 안쪽 보상의 DTO 를 순서대로 모은다.
+    // 숨김 묶음 안의 보상은 is_hidden 을 켜서 내보낸다
+    pub fn to_dtos(&self) -> Vec<RewardDto> {
+        match self {
+            Reward::Bundle {
+                rewards, is_hidden, ..
+            } => {
+                let mut dtos: Vec<RewardDto> = rewards.iter().flat_map(Reward::to_dtos).collect();
+                for dto in &mut dtos {
+                    dto.is_hidden |= *is_hidden;
+                }
+                dtos
+            }
+            reward => reward.to_dto().into_iter().collect(),
+        }
+    }
+
+    // 묶음을 flatten 한 뒤 같은 재화의 Gem 을 하나로 합친다. 아직 굴리지 않은 범위 Gem,
+    // 숨김 여부가 다른 Gem, 숨김 묶음 안의 Gem, 합치면 넘치는 Gem 은 따로 둔다
+    pub fn merge(self) -> Self {
+        let (name, is_hidden) = match &self {
+            Reward::Bundle {
+                name, is_hidden, ..
+            } => (name.clone(), *is_hidden),
+            _ => return self,
+        };
+        let mut rewards: Vec<Reward> = Vec::new();
+        for reward in self.flatten() {
+            let target = rewards
+                .iter_mut()
+                .find(|other| other.merges_with(&reward))
+                .and_then(|other| match (other, &reward) {
+                    (Reward::Gem { delta, .. }, Reward::Gem { delta: more, .. }) => {
+                        Some((delta.checked_add(*more)?, delta))
+                    }
+                    _ => None,
+                });
+            match target {
+                Some((sum, delta)) => *delta = sum,
+                None => rewards.push(reward),
+            }
+        }
+        Reward::Bundle {
+            name,
+            rewards,
+            is_hidden,
+        }
+    }
+
+    fn merges_with(&self, other: &Reward) -> bool {
+        match (self, other) {
+            (
+                Reward::Gem {
+                    currency,
+                    min: None,
+                    max: None,
+                    name,
+                    shelf_life,
+                    is_hidden,
+                    ..
+                },
+                Reward::Gem {
+                    currency: other_currency,
+                    min: None,
+                    max: None,
+                    name: other_name,
+                    shelf_life: other_shelf_life,
+                    is_hidden: other_hidden,
+                    ..
                 },
-                false => Reward::Unknown,
+            ) => {
+                currency == other_currency
+                    && name == other_name
+                    && shelf_life == other_shelf_life
+                    && is_hidden == other_hidden
+            }
+            _ => false,
+        }
+    }
+
+    // 저장/재전송용 DTO. TryFrom 이 받은 필드를 그대로 돌려준다
+    pub fn to_dto(&self) -> Option<RewardDto> {
+        let dto = match self {
+            Reward::Item {
+                item_type,
+                url,
+                name,
+                shelf_life,
+                delta,
+                code,
+                is_hidden,
+            } => RewardDto {
+                _type: item_type.clone(),
+                name: Some(name.clone()),
+                url: Some(url.clone()),
+                code: code.clone(),
+                delta: *delta,
+                is_hidden: *is_hidden,
+                shelf_life: *shelf_life,
+                ..RewardDto::empty(RewardGroup::ITEM)
+            },
+            Reward::Gem {
+                currency,
+                delta,
+                min,
+                max,
+                name,
+                shelf_life,
+                is_hidden,
+            } => RewardDto {
+                _type: currency.clone(),
+                delta: *delta,
+                min: *min,
+                max: *max,
+                name: name.clone(),
+                shelf_life: *shelf_life,
+                is_hidden: *is_hidden,
+                ..RewardDto::empty(RewardGroup::ASSET)
+            },
+            Reward::Avatar {
+                cosmetic_id,
+                url,
+                name,
+                delta,
+                is_hidden,
+            } => RewardDto {
+                _type: cosmetic_id.clone(),
+                url: Some(url.clone()),
+                name: name.clone(),
+                delta: *delta,
+                is_hidden: *is_hidden,
+                ..RewardDto::empty(RewardGroup::AVATAR)
             },
-            _ => Reward::Unknown,
+            Reward::Coupon {
+                coupon_type,
+                code,
+                name,
+                shelf_life,
+                delta,
+                is_hidden,
+                ..
+            } => RewardDto {
+                _type: coupon_type.clone(),
+                name: name.clone(),
+                code: Some(code.clone()),
+                delta: *delta,
+                is_hidden: *is_hidden,
+                shelf_life: *shelf_life,
+                ..RewardDto::empty(RewardGroup::COUPON)
+            },
+            Reward::Quest {
+                quest_id,
+                name,
+                required,
+                url,
+                code,
+                shelf_life,
+                is_hidden,
+            } => RewardDto {
+                _type: quest_id.clone(),
+                name: Some(name.clone()),
+                delta: *required,
+                url: url.clone(),
+                code: code.clone(),
+                shelf_life: *shelf_life,
+                is_hidden: *is_hidden,
+                ..RewardDto::empty(RewardGroup::QUEST)
+            },
+            Reward::Bundle { .. } | Reward::Unknown => return None,
+        };
+        Some(dto)
+    }
+
+    // 쿠폰이 아니거나 이미 사용했으면 false
+    pub fn redeem(&mut self, clock: &dyn Clock) -> bool {
+        match self {
+            Reward::Coupon { state, .. } => state.redeem(clock.now()),
+            _ => false,
         }
     }
 }
 
-trait TimeLimited {
-    fn has_expired(&self) -> bool;
+pub trait TimeLimited {
+    fn has_expired(&self, clock: &dyn Clock) -> bool;
     fn fmt_expire_time(&self) -> String;
 }
 
-trait RewardDisplay {
+pub trait RewardDisplay {
     fn is_hidden(&self) -> bool;
     fn unit_image(&self) -> &str;
     fn image(&self) -> &str;
     fn fmt_string(&self) -> String;
     fn get_shelf_life(&self) -> Option<DateTime<Utc>>;
-    fn has_expired(&self) -> bool {
+}
+
+// 유통기한이 있는 보상(Item, Gem, Coupon, Quest 와 그것을 담은 묶음)만 만료된다. 나머지는 항상 false / ""
+impl TimeLimited for Reward {
+    fn has_expired(&self, clock: &dyn Clock) -> bool {
         match self.get_shelf_life() {
-            Some(d) => d < Utc::now(),
+            Some(d) => d < clock.now(),
             None => false,
         }
     }
@@ -72,7 +375,11 @@ impl RewardDisplay for Reward {
         match self {
             Reward::Gem { is_hidden, .. } => *is_hidden,
             Reward::Item { is_hidden, .. } => *is_hidden,
-            _ => false,
+            Reward::Avatar { is_hidden, .. } => *is_hidden,
+            Reward::Coupon { is_hidden, .. } => *is_hidden,
+            Reward::Quest { is_hidden, .. } => *is_hidden,
+            Reward::Bundle { is_hidden, .. } => *is_hidden,
+            Reward::Unknown => false,
         }
     }
 
@@ -80,7 +387,11 @@ impl RewardDisplay for Reward {
         match self {
             Reward::Gem { .. } => "Gem Image",
             Reward::Item { .. } => "Item Image",
-            _ => "",
+            Reward::Avatar { .. } => "Avatar Image",
+            Reward::Coupon { .. } => "Coupon Image",
+            Reward::Quest { .. } => "Quest Image",
+            Reward::Bundle { .. } => "Bundle Image",
+            Reward::Unknown => "",
         }
     }
 
@@ -88,27 +399,67 @@ impl RewardDisplay for Reward {
         match self {
             Reward::Gem { .. } => "Gem Image",
             Reward::Item { url, .. } => url,
-            _ => "",
+            Reward::Avatar { url, .. } => url,
+            Reward::Coupon { .. } => "Coupon Image",
+            Reward::Quest { .. } => "Quest Image",
+            Reward::Bundle { .. } => "Bundle Image",
+            Reward::Unknown => "",
         }
     }
 
     fn fmt_string(&self) -> String {
         match self {
+            Reward::Gem {
+                min: Some(min),
+                max: Some(max),
+                ..
+            } => format!("{}–{} Gems", min, max),
             Reward::Gem { delta, .. } => format!("{} Gems", delta),
             Reward::Item { name, .. } => format!("{} Item", name),
-            _ => String::from(""),
+            Reward::Avatar { cosmetic_id, .. } => format!("{} Avatar", cosmetic_id),
+            Reward::Coupon {
+                code, name, state, ..
+            } => {
+                let label = name.as_deref().unwrap_or(code);
+                match state {
+                    CouponState::Unredeemed => format!("{} Coupon", label),
+                    CouponState::Redeemed(_) => format!("{} Coupon (redeemed)", label),
+                }
+            }
+            Reward::Quest { name, required, .. } => format!("{} Quest x{}", name, required),
+            // "Spring Chest (3000 Gems + FRAME_GOLD Avatar)". 숨김 보상은 빠진다
+            Reward::Bundle { name, rewards, .. } => {
+                let children: Vec<_> = rewards
+                    .iter()
+                    .filter(|reward| !reward.is_hidden())
+                    .map(|reward| reward.fmt_string())
+                    .collect();
+                match children.is_empty() {
+                    true => name.clone(),
+                    false => format!("{} ({})", name, children.join(" + ")),
+                }
+            }
+            Reward::Unknown => String::from(""),
         }
     }
 
     fn get_shelf_life(&self) -> Option<DateTime<Utc>> {
         match self {
             Reward::Item { shelf_life, .. } => *shelf_life,
+            Reward::Gem { shelf_life, .. } => *shelf_life,
+            Reward::Coupon { shelf_life, .. } => *shelf_life,
+            Reward::Quest { shelf_life, .. } => *shelf_life,
+            // 묶음은 안쪽에서 가장 먼저 만료되는 보상을 따른다
+            Reward::Bundle { rewards, .. } => {
+                rewards.iter().filter_map(Reward::get_shelf_life).min()
+            }
             _ => None,
         }
     }
 }
 
 pub fn test() {
+    let clock = ManualClock::new(SystemClock.now());
     let dto = RewardDto {
         group: RewardGroup::ASSET,
         delta: 3000,
@@ -134,11 +485,24 @@ pub fn test() {
         min: None,
         max: None,
         is_hidden: false,
-        shelf_life: Some(Utc::now()),
+        shelf_life: Some(clock.now() + chrono::Duration::hours(1)),
+    };
+
+    let dto_invalid = RewardDto {
+        _type: String::from("*@#&^$*@&#^$"),
+        min: Some(500),
+        max: Some(100),
+        ..dto.clone()
     };
 
-    let reward = Reward::from(dto);
-    let reward2 = Reward::from(dto2);
+    let dto_ranged = RewardDto {
+        min: Some(100),
+        max: Some(500),
+        ..dto.clone()
+    };
+
+    let reward = Reward::try_from(dto).unwrap();
+    let reward2 = Reward::try_from(dto2).unwrap();
 
     println!("v2 reward1: {}", reward.fmt_string());
     println!("v2 reward1: {}", reward.image());
@@ -148,4 +512,77 @@ pub fn test() {
     println!("v2 reward2: {}", reward2.image());
     println!("v2 reward2: {}", reward2.unit_image());
     println!("v2 reward2: {}", reward2.fmt_expire_time());
+    println!("v2 reward2 expired: {}", reward2.has_expired(&clock));
+    clock.advance(chrono::Duration::hours(2));
+    println!("v2 reward2 expired: {} (+2h)", reward2.has_expired(&clock));
+
+    let mut reward3 = Reward::try_from(dto_ranged).unwrap();
+    println!("v2 reward3: {}", reward3.fmt_string());
+    reward3.roll(&mut SeededRng::new(42));
+    println!("v2 reward3: {}", reward3.fmt_string());
+
+    if let Err(err) = Reward::try_from(dto_invalid) {
+        println!("v2 invalid: {err}");
+    }
+
+    let dto_coupon = RewardDto {
+        _type: String::from("WELCOME"),
+        name: Some(String::from("Welcome Pack")),
+        code: Some(String::from("PLAYIO-2023")),
+        shelf_life: Some(clock.now() + chrono::Duration::days(1)),
+        ..RewardDto::empty(RewardGroup::COUPON)
+    };
+    let dto_quest = RewardDto {
+        _type: String::from("DAILY_LOGIN"),
+        name: Some(String::from("Daily Login")),
+        delta: 7,
+        ..RewardDto::empty(RewardGroup::QUEST)
+    };
+    let mut coupon = Reward::try_from(dto_coupon).unwrap();
+    let quest = Reward::try_from(dto_quest).unwrap();
+    println!("v2 coupon: {}", coupon.fmt_string());
+    coupon.redeem(&clock);
+    println!("v2 coupon: {}", coupon.fmt_string());
+    println!("v2 quest: {}", quest.fmt_string());
+
+    let xp = |delta| Reward::Gem {
+        currency: String::from("XP"),
+        delta,
+        min: None,
+        max: None,
+        name: None,
+        shelf_life: None,
+        is_hidden: false,
+    };
+    let chest = Reward::bundle(
+        "Spring Chest",
+        vec![
+            xp(1000),
+            Reward::bundle(
+                "Star Pack",
+                vec![
+                    xp(500),
+                    Reward::Avatar {
+                        cosmetic_id: String::from("FRAME_GOLD"),
+                        url: String::from("https://static.playio.club/__asset/frame_gold.png"),
+                        name: None,
+                        delta: 1,
+                        is_hidden: false,
+                    },
+                ],
+            ),
+        ],
+    );
+    println!("v2 chest: {}", chest.fmt_string());
+    println!("v2 chest merged: {}", chest.merge().fmt_string());
+
+    let sweeper = Sweeper::new(&clock).with_grace(chrono::Duration::minutes(30));
+    let sweep = sweeper.sweep([reward, reward2, coupon, quest]);
+    for line in sweep.report() {
+        println!("v2 swept: {line}");
+    }
+    println!("v2 live: {}", sweep.live.len());
+    if let Some(next) = sweeper.next_expiry(&sweep.live) {
+        println!("v2 next sweep: {}", next.label());
+    }
 }
@@ -1,10 +0,0 @@
-pub mod version_1 {
-    pub fn test() {
-        println!("other version_1");
-    }
-}
-pub mod version_2 {
-    pub fn test() {
-        println!("other version_2");
-    }
-}
@@ -0,0 +1,205 @@
+// 여러 테스트 파일이 같이 쓰는 픽스처와 RewardDto 생성 전략
+#![allow(dead_code)]
+
+use chrono::{DateTime, TimeZone, Utc};
+use proptest::prelude::*;
+use trait_practice::object::reward::{RewardDto, RewardGroup};
+use trait_practice::object::version_1::Gem;
+
+// 테스트 시계가 멈춰 있는 시각
+pub fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2023, 7, 1, 0, 0, 0).unwrap()
+}
+
+// _type 이 currency 인 ASSET DTO
+pub fn asset(currency: &str, delta: u32) -> RewardDto {
+    RewardDto {
+        _type: String::from(currency),
+        delta,
+        ..RewardDto::empty(RewardGroup::ASSET)
+    }
+}
+
+// 범위도 이름도 없는 v1 Gem
+pub fn gem_reward(currency: &str, delta: u32) -> Gem {
+    Gem {
+        currency: String::from(currency),
+        delta,
+        min: None,
+        max: None,
+        name: None,
+        shelf_life: None,
+        is_hidden: false,
+    }
+}
+
+pub fn text() -> impl Strategy<Value = String> {
+    "[A-Za-z0-9_ ,.-]{1,24}"
+}
+
+pub fn shelf_life() -> impl Strategy<Value = Option<DateTime<Utc>>> {
+    proptest::option::of(
+        (0i64..4_102_444_800, 0u32..1_000_000_000)
+            .prop_map(|(secs, nanos)| Utc.timestamp_opt(secs, nanos).unwrap()),
+    )
+}
+
+// min <= max 인 범위 또는 범위 없음
+pub fn range() -> impl Strategy<Value = (Option<u32>, Option<u32>)> {
+    prop_oneof![
+        Just((None, None)),
+        (any::<u32>(), any::<u32>()).prop_map(|(a, b)| (Some(a.min(b)), Some(a.max(b)))),
+    ]
+}
+
+pub fn gem() -> impl Strategy<Value = RewardDto> {
+    (
+        text(),
+        any::<u32>(),
+        range(),
+        proptest::option::of(text()),
+        any::<bool>(),
+        shelf_life(),
+    )
+        .prop_map(
+            |(_type, delta, (min, max), name, is_hidden, shelf_life)| RewardDto {
+                _type,
+                delta,
+                min,
+                max,
+                name,
+                is_hidden,
+                shelf_life,
+                ..RewardDto::empty(RewardGroup::ASSET)
+            },
+        )
+}
+
+pub fn item() -> impl Strategy<Value = RewardDto> {
+    (
+        text(),
+        text(),
+        text(),
+        (any::<u32>(), proptest::option::of(text())),
+        any::<bool>(),
+        shelf_life(),
+    )
+        .prop_map(
+            |(_type, name, url, (delta, code), is_hidden, shelf_life)| RewardDto {
+                _type,
+                name: Some(name),
+                url: Some(url),
+                code,
+                delta,
+                is_hidden,
+                shelf_life,
+                ..RewardDto::empty(RewardGroup::ITEM)
+            },
+        )
+}
+
+pub fn avatar() -> impl Strategy<Value = RewardDto> {
+    (
+        text(),
+        text(),
+        proptest::option::of(text()),
+        any::<u32>(),
+        any::<bool>(),
+    )
+        .prop_map(|(_type, url, name, delta, is_hidden)| RewardDto {
+            _type,
+            url: Some(url),
+            name,
+            delta,
+            is_hidden,
+            ..RewardDto::empty(RewardGroup::AVATAR)
+        })
+}
+
+pub fn coupon() -> impl Strategy<Value = RewardDto> {
+    (
+        text(),
+        proptest::option::of(text()),
+        text(),
+        (any::<u32>(), any::<bool>()),
+        shelf_life(),
+    )
+        .prop_map(
+            |(_type, name, code, (delta, is_hidden), shelf_life)| RewardDto {
+                _type,
+                name,
+                code: Some(code),
+                delta,
+                is_hidden,
+                shelf_life,
+                ..RewardDto::empty(RewardGroup::COUPON)
+            },
+        )
+}
+
+pub fn quest() -> impl Strategy<Value = RewardDto> {
+    (
+        text(),
+        text(),
+        any::<u32>(),
+        proptest::option::of(text()),
+        proptest::option::of(text()),
+        (any::<bool>(), shelf_life()),
+    )
+        .prop_map(
+            |(_type, name, delta, url, code, (is_hidden, shelf_life))| RewardDto {
+                _type,
+                name: Some(name),
+                delta,
+                url,
+                code,
+                is_hidden,
+                shelf_life,
+                ..RewardDto::empty(RewardGroup::QUEST)
+            },
+        )
+}
+
+// builtin 레지스트리와 v2 가 모두 받아들이는 DTO
+pub fn valid_dto() -> impl Strategy<Value = RewardDto> {
+    prop_oneof![gem(), item(), avatar(), coupon(), quest()]
+}
+
+// 필드가 빠졌거나 범위가 뒤집혔거나 그룹을 모르는 DTO 까지 포함
+pub fn any_dto() -> impl Strategy<Value = RewardDto> {
+    (
+        prop_oneof![
+            Just(RewardGroup::ASSET),
+            Just(RewardGroup::ITEM),
+            Just(RewardGroup::AVATAR),
+            Just(RewardGroup::COUPON),
+            Just(RewardGroup::QUEST),
+            text().prop_map(|group| RewardGroup::Unknown(format!("?{group}"))),
+        ],
+        text(),
+        proptest::option::of(text()),
+        proptest::option::of(text()),
+        proptest::option::of(text()),
+        (
+            any::<u32>(),
+            proptest::option::of(0u32..10),
+            proptest::option::of(0u32..10),
+        ),
+        any::<bool>(),
+        shelf_life(),
+    )
+        .prop_map(
+            |(group, _type, name, url, code, (delta, min, max), is_hidden, shelf_life)| RewardDto {
+                group,
+                _type,
+                name,
+                url,
+                code,
+                delta,
+                min,
+                max,
+                is_hidden,
+                shelf_life,
+            },
+        )
+}
@@ -0,0 +1,226 @@
+#![cfg(feature = "object")]
+
+mod common;
+
+use chrono::Duration;
+use common::{asset, start};
+use std::collections::HashSet;
+use trait_practice::object::clock::{Clock, ManualClock};
+use trait_practice::object::coupon::{
+    CouponCode, CouponRules, CouponService, CouponStore, IssueError, MemoryCouponStore,
+    RedeemError, RedeemLimits, Redemption, Uses,
+};
+use trait_practice::object::registry::RewardRegistry;
+use trait_practice::object::rng::{RewardRng, SeededRng};
+use trait_practice::object::version_1::Coupon;
+
+fn service() -> CouponService<MemoryCouponStore, ManualClock> {
+    CouponService::default().with_clock(ManualClock::new(start()))
+}
+
+#[test]
+fn generated_codes_are_unique_and_checked() {
+    let mut rng = SeededRng::new(1);
+    let codes: HashSet<_> = (0..10_000)
+        .map(|_| CouponCode::generate(&mut rng))
+        .collect();
+    assert_eq!(codes.len(), 10_000);
+    for code in &codes {
+        let shown = code.to_string();
+        assert_eq!(CouponCode::parse(&shown).as_ref(), Ok(code));
+        assert_eq!(CouponCode::parse(&shown.to_lowercase()).as_ref(), Ok(code));
+    }
+}
+
+#[test]
+fn typos_and_swaps_fail_the_checksum() {
+    let code = CouponCode::generate(&mut SeededRng::new(2));
+    let chars: Vec<char> = code.as_str().chars().collect();
+    for i in 0..chars.len() {
+        for c in "0123456789ABCDEFGHJKMNPQRSTVWXYZ".chars() {
+            if c == chars[i] {
+                continue;
+            }
+            let mut typo = chars.clone();
+            typo[i] = c;
+            let typo: String = typo.into_iter().collect();
+            assert!(CouponCode::parse(&typo).is_err(), "{typo}");
+        }
+        if i + 1 < chars.len() && chars[i] != chars[i + 1] {
+            let mut swapped = chars.clone();
+            swapped.swap(i, i + 1);
+            let swapped: String = swapped.into_iter().collect();
+            assert!(CouponCode::parse(&swapped).is_err(), "{swapped}");
+        }
+    }
+    assert!(matches!(
+        CouponCode::parse("NOT-A-CODE"),
+        Err(RedeemError::InvalidCode(_))
+    ));
+}
+
+#[test]
+fn single_use_coupon() {
+    let mut service = service();
+    let campaign = service
+        .issue(
+            &mut SeededRng::new(3),
+            CouponRules::default(),
+            vec![asset("XP", 100)],
+        )
+        .unwrap();
+    let code = campaign.code.to_string();
+    assert_eq!(service.redeem(&code, "alice"), Ok(vec![asset("XP", 100)]));
+    assert_eq!(
+        service.redeem(&code, "alice"),
+        Err(RedeemError::AlreadyRedeemed {
+            player: String::from("alice")
+        })
+    );
+    assert_eq!(service.redeem(&code, "bob"), Err(RedeemError::Exhausted));
+}
+
+#[test]
+fn multi_use_coupon_with_player_limit() {
+    let mut service = service();
+    let rules = CouponRules {
+        uses: Uses::Limited(3),
+        per_player: Some(2),
+        ..CouponRules::default()
+    };
+    let code = service
+        .issue(&mut SeededRng::new(4), rules, vec![asset("XP", 5)])
+        .unwrap()
+        .code
+        .to_string();
+    assert!(service.redeem(&code, "alice").is_ok());
+    assert!(service.redeem(&code, "alice").is_ok());
+    assert!(matches!(
+        service.redeem(&code, "alice"),
+        Err(RedeemError::AlreadyRedeemed { .. })
+    ));
+    assert!(service.redeem(&code, "bob").is_ok());
+    assert_eq!(service.redeem(&code, "carol"), Err(RedeemError::Exhausted));
+
+    let unlimited = CouponRules {
+        uses: Uses::Unlimited,
+        ..CouponRules::default()
+    };
+    let code = service
+        .issue(&mut SeededRng::new(5), unlimited, vec![asset("XP", 1)])
+        .unwrap()
+        .code
+        .to_string();
+    for _ in 0..100 {
+        assert!(service.redeem(&code, "alice").is_ok());
+    }
+}
+
+#[test]
+fn validity_window() {
+    let mut service = service();
+    let now = service.clock.now();
+    let rules = CouponRules {
+        valid_from: Some(now + Duration::days(1)),
+        valid_until: Some(now + Duration::days(2)),
+        ..CouponRules::default()
+    };
+    let code = service
+        .issue(&mut SeededRng::new(6), rules, vec![asset("XP", 1)])
+        .unwrap()
+        .code
+        .to_string();
+    assert_eq!(
+        service.redeem(&code, "alice"),
+        Err(RedeemError::NotYetValid {
+            from: now + Duration::days(1)
+        })
+    );
+    service.clock.advance(Duration::days(3));
+    assert_eq!(
+        service.redeem(&code, "alice"),
+        Err(RedeemError::Expired {
+            at: now + Duration::days(2)
+        })
+    );
+}
+
+#[test]
+fn unknown_code_is_not_found() {
+    let mut service = service();
+    let code = CouponCode::generate(&mut SeededRng::new(7));
+    assert_eq!(
+        service.redeem(code.as_str(), "alice"),
+        Err(RedeemError::NotFound(code))
+    );
+}
+
+#[test]
+fn coupon_reward_carries_a_redeemable_code() {
+    let mut service = service();
+    let campaign = service
+        .issue(
+            &mut SeededRng::new(8),
+            CouponRules::default(),
+            vec![asset("XP", 1000)],
+        )
+        .unwrap();
+    let reward = RewardRegistry::builtin()
+        .build(campaign.reward_dto("WELCOME"))
+        .unwrap();
+    let coupon = reward.downcast_ref::<Coupon>().unwrap();
+    assert_eq!(
+        service.redeem(&coupon.code, "alice"),
+        Ok(vec![asset("XP", 1000)])
+    );
+}
+
+// 항상 같은 코드를 만드는 rng
+struct StuckRng;
+
+impl RewardRng for StuckRng {
+    fn next_u64(&mut self) -> u64 {
+        0
+    }
+}
+
+#[test]
+fn issue_gives_up_after_repeated_collisions() {
+    let mut service = service();
+    let first = service.issue(&mut StuckRng, CouponRules::default(), vec![asset("XP", 1)]);
+    assert!(first.is_ok());
+    let err = service
+        .issue(&mut StuckRng, CouponRules::default(), vec![asset("XP", 1)])
+        .unwrap_err();
+    assert_eq!(err, IssueError::CodeCollision { attempts: 16 });
+    assert_eq!(err.to_string(), "no unused coupon code after 16 attempts");
+}
+
+#[test]
+fn try_record_checks_limits_and_records_together() {
+    let mut store = MemoryCouponStore::new();
+    let code = CouponCode::generate(&mut SeededRng::new(9));
+    let at = start();
+    let by = |player: &str| Redemption {
+        player: String::from(player),
+        at,
+    };
+    let limits = RedeemLimits {
+        total: Some(2),
+        per_player: Some(1),
+    };
+    assert_eq!(store.try_record(&code, by("alice"), limits), Ok(()));
+    assert_eq!(
+        store.try_record(&code, by("alice"), limits),
+        Err(RedeemError::AlreadyRedeemed {
+            player: String::from("alice")
+        })
+    );
+    assert_eq!(store.try_record(&code, by("bob"), limits), Ok(()));
+    assert_eq!(
+        store.try_record(&code, by("carol"), limits),
+        Err(RedeemError::Exhausted)
+    );
+    // 거절된 사용은 기록되지 않는다
+    assert_eq!(store.redemptions(&code), [by("alice"), by("bob")]);
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "object")]
+
+mod common;
+
+use common::gem_reward;
+use trait_practice::object::version_1::{Gem, Item, RewardObj, RewardObjExt};
+
+const GEM: &str = "trait_practice::object::version_1::Gem";
+const ITEM: &str = "trait_practice::object::version_1::Item";
+
+#[test]
+fn downcast_ref_and_mut_reach_the_concrete_reward() {
+    let mut reward: RewardObj = Box::new(gem_reward("XP", 100));
+    assert!(reward.is::<Gem>());
+    assert!(!reward.is::<Item>());
+    reward.downcast_mut::<Gem>().unwrap().delta = 300;
+    assert_eq!(reward.downcast_ref::<Gem>().unwrap().delta, 300);
+    assert_eq!(reward.type_name(), GEM);
+}
+
+#[test]
+fn cast_error_names_expected_and_actual_types() {
+    let mut reward: RewardObj = Box::new(gem_reward("XP", 100));
+    let err = reward.downcast_ref::<Item>().err().unwrap();
+    assert_eq!((err.expected, err.actual), (ITEM, GEM));
+    assert_eq!(err.to_string(), format!("expected {ITEM}, found {GEM}"));
+
+    let err = reward.downcast_mut::<Item>().err().unwrap();
+    assert_eq!((err.expected, err.actual), (ITEM, GEM));
+}
+
+#[test]
+fn owned_downcast_returns_the_reward_on_failure() {
+    let reward: RewardObj = Box::new(gem_reward("XP", 100));
+    let err = reward.downcast::<Item>().err().unwrap();
+    assert_eq!((err.expected, err.actual), (ITEM, GEM));
+    // 실패해도 보상을 잃지 않는다
+    let gem = err.reward.downcast::<Gem>().unwrap();
+    assert_eq!(gem.delta, 100);
+}
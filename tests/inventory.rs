@@ -0,0 +1,136 @@
+#![cfg(feature = "object")]
+
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::gem_reward;
+use trait_practice::object::inventory::{grant_all, GrantError, Granted, Inventory, ItemStack};
+use trait_practice::object::version_1::{Avatar, Gem, Item, RewardObj};
+
+fn item(delta: u32) -> RewardObj {
+    Box::new(Item {
+        item_type: String::from("POUCH"),
+        url: String::from("https://static.playio.club/pouch.png"),
+        name: String::from("Gem Pouch"),
+        shelf_life: Some(Utc.with_ymd_and_hms(2023, 7, 1, 0, 0, 0).unwrap()),
+        delta,
+        code: None,
+        is_hidden: false,
+    })
+}
+
+fn avatar() -> RewardObj {
+    Box::new(Avatar {
+        cosmetic_id: String::from("FRAME_GOLD"),
+        url: String::from("https://static.playio.club/frame_gold.png"),
+        name: None,
+        delta: 1,
+        is_hidden: false,
+    })
+}
+
+#[test]
+fn receipt_lists_every_reward_in_order() {
+    let mut inventory = Inventory::new();
+    let rewards: [RewardObj; 5] = [
+        Box::new(gem_reward("XP", 100)),
+        item(2),
+        avatar(),
+        Box::new(gem_reward("XP", 50)),
+        item(3),
+    ];
+    let receipt = grant_all(&mut inventory, &rewards).unwrap();
+
+    let shelf_life = Some(Utc.with_ymd_and_hms(2023, 7, 1, 0, 0, 0).unwrap());
+    let pouch = |quantity| Granted::Item {
+        name: String::from("Gem Pouch"),
+        quantity,
+        shelf_life,
+    };
+    assert_eq!(
+        receipt.granted,
+        [
+            Granted::Currency {
+                currency: String::from("XP"),
+                delta: 100,
+                balance: 100,
+            },
+            pouch(2),
+            Granted::Skipped,
+            Granted::Currency {
+                currency: String::from("XP"),
+                delta: 50,
+                balance: 150,
+            },
+            pouch(3),
+        ]
+    );
+    assert_eq!(inventory.balance("XP"), 150);
+    // 같은 이름, 같은 유통기한이라 한 스택으로 합쳐진다
+    assert_eq!(
+        inventory.items,
+        [ItemStack {
+            name: String::from("Gem Pouch"),
+            url: String::from("https://static.playio.club/pouch.png"),
+            quantity: 5,
+            shelf_life,
+        }]
+    );
+}
+
+#[test]
+fn overflow_rolls_back_every_reward() {
+    let mut inventory = Inventory::new();
+    inventory.wallet.insert(String::from("STAR"), u64::MAX - 10);
+    let before = inventory.clone();
+
+    let rewards: [RewardObj; 4] = [
+        Box::new(gem_reward("XP", 100)),
+        item(1),
+        Box::new(gem_reward("STAR", 10)),
+        Box::new(gem_reward("STAR", 1)),
+    ];
+    assert_eq!(
+        grant_all(&mut inventory, &rewards),
+        Err(GrantError::Overflow {
+            target: String::from("STAR")
+        })
+    );
+    assert_eq!(inventory, before);
+
+    // 실패한 뒤에도 인벤토리는 그대로 쓸 수 있다
+    let receipt = grant_all(&mut inventory, &rewards[..3]).unwrap();
+    assert_eq!(receipt.granted.len(), 3);
+    assert_eq!(inventory.balance("STAR"), u64::MAX);
+    assert_eq!(inventory.balance("XP"), 100);
+}
+
+#[test]
+fn unrolled_gem_rolls_back_every_reward() {
+    let mut inventory = Inventory::new();
+    let unrolled: RewardObj = Box::new(Gem {
+        currency: String::from("STAR"),
+        delta: 0,
+        min: Some(1),
+        max: Some(10),
+        name: None,
+        shelf_life: None,
+        is_hidden: false,
+    });
+    let rewards = [Box::new(gem_reward("XP", 100)), item(1), unrolled];
+    assert_eq!(
+        grant_all(&mut inventory, &rewards),
+        Err(GrantError::Unrolled {
+            currency: String::from("STAR")
+        })
+    );
+    assert_eq!(inventory, Inventory::new());
+}
+
+#[test]
+fn empty_grant_is_an_empty_receipt() {
+    let mut inventory = Inventory::new();
+    let receipt = grant_all(&mut inventory, &[]).unwrap();
+    assert!(receipt.granted.is_empty());
+    assert_eq!(inventory, Inventory::new());
+}
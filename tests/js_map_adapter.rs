@@ -0,0 +1,186 @@
+#![cfg(feature = "js_map")]
+
+use std::borrow::Borrow;
+use std::cell::Cell;
+use std::collections::{btree_map, BTreeMap, HashMap};
+use std::hash::Hash;
+use std::ops::ControlFlow;
+use trait_practice::js_map::ordered::OrderedJsMap;
+use trait_practice::js_map::version_1;
+use trait_practice::js_map::version_2::{self, DynJsMap, JsMap, JsMapIterator, JsMapMut};
+
+// version_1 만 구현한 맵. 키 순서로 순회한다
+#[derive(Default)]
+struct Sorted(BTreeMap<String, i64>);
+
+impl<'a> version_1::JsMap<'a> for Sorted {
+    type Key = String;
+    type Value = i64;
+    type EntryIter = btree_map::Iter<'a, String, i64>;
+
+    fn get_value<Q>(&self, key: &Q) -> Option<&i64>
+    where
+        String: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        self.0.get(key)
+    }
+
+    fn entries(&'a self) -> Self::EntryIter {
+        self.0.iter()
+    }
+}
+
+impl<'a> version_1::JsMapMut<'a> for Sorted {
+    fn set(&mut self, key: String, value: i64) -> &mut Self {
+        self.0.insert(key, value);
+        self
+    }
+
+    fn delete<Q>(&mut self, key: &Q) -> bool
+    where
+        String: Borrow<Q>,
+        Q: ?Sized + Hash + Ord,
+    {
+        self.0.remove(key).is_some()
+    }
+
+    fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    fn size(&self) -> usize {
+        self.0.len()
+    }
+}
+
+fn pairs() -> [(String, i64); 3] {
+    [("c", 3), ("a", 1), ("b", 2)].map(|(k, v)| (k.to_owned(), v))
+}
+
+fn maps() -> Vec<Box<dyn JsMap<String, i64>>> {
+    vec![
+        Box::new(Vec::from(pairs())),
+        Box::new(HashMap::from(pairs())),
+        Box::new(OrderedJsMap::from_iter(pairs())),
+        Box::new(DynJsMap(Sorted(BTreeMap::from(pairs())))),
+    ]
+}
+
+fn sorted_entries(map: &dyn JsMap<String, i64>) -> Vec<(String, i64)> {
+    let mut entries = Vec::new();
+    map.for_each_entry(&mut |k, v| entries.push((k.clone(), *v)));
+    entries.sort();
+    entries
+}
+
+// version_1 제네릭 함수
+fn sum_v1<'a>(map: &'a impl version_1::JsMap<'a, Key = String, Value = i64>) -> i64 {
+    map.entries().map(|(_, v)| v).sum()
+}
+
+#[test]
+fn heterogeneous_maps_share_one_api() {
+    let key = String::from("b");
+    let mut expected = Vec::from(pairs());
+    expected.sort();
+    for map in maps() {
+        assert_eq!(map.get_value(&key), Some(&2));
+        assert_eq!(sorted_entries(map.as_ref()), expected);
+        assert_eq!(map.entries().count(), 3);
+    }
+    let sorted = &maps()[3];
+    let keys: Vec<&String> = sorted.keys().collect();
+    assert_eq!(keys, ["a", "b", "c"]);
+}
+
+#[test]
+fn v1_map_is_mutable_through_v2() {
+    let mut map: Box<dyn JsMapMut<String, i64>> = Box::new(DynJsMap(Sorted::default()));
+    map.set(String::from("x"), 1).set(String::from("y"), 2);
+    assert!(map.delete("x"));
+    assert!(!map.has(&String::from("x")));
+    assert_eq!(map.size(), 1);
+    map.clear();
+    assert_eq!(map.size(), 0);
+}
+
+#[test]
+fn v2_trait_objects_are_v1_maps() {
+    for map in maps() {
+        assert_eq!(sum_v1(&map), 6);
+        assert_eq!(version_1::JsMap::get_value(map.as_ref(), "b"), Some(&2));
+        let keys = version_1::JsMap::keys(map.as_ref()).count();
+        assert_eq!(keys, 3);
+    }
+
+    let mut map: Box<dyn version_2::JsMapMut<String, i64>> = Box::new(Vec::new());
+    version_1::JsMapMut::set(&mut map, String::from("a"), 10);
+    assert_eq!(sum_v1(&map), 10);
+    assert_eq!(version_1::JsMapMut::size(&map), 1);
+}
+
+// 키를 빌린 version_1 맵은 감쌀 수 없으니 키를 소유 타입으로 바꿔서 감싼다
+#[test]
+fn borrowed_keys_are_owned_before_wrapping() {
+    let text = String::from("mu ya ho");
+    let borrowed: Vec<(&str, i64)> = text.split(' ').zip(1..).collect();
+    let owned: Vec<(Box<str>, i64)> = borrowed.iter().map(|&(k, v)| (Box::from(k), v)).collect();
+    let map: Box<dyn JsMap<Box<str>, i64>> = Box::new(DynJsMap(owned));
+    assert_eq!(map.get_value("ya"), Some(&2));
+    assert_eq!(map.entries().count(), 3);
+}
+
+// get_value 와 순회를 몇 번 불렀는지 센다
+#[derive(Default)]
+struct Probe {
+    map: HashMap<String, i64>,
+    lookups: Cell<u32>,
+    scans: Cell<u32>,
+}
+
+impl JsMap<String, i64> for Probe {
+    fn get_value(&self, key: &str) -> Option<&i64> {
+        self.lookups.set(self.lookups.get() + 1);
+        self.map.get(key)
+    }
+
+    fn try_for_each_entry<'a>(
+        &'a self,
+        f: &mut dyn FnMut(&'a String, &'a i64) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.scans.set(self.scans.get() + 1);
+        self.map.iter().try_for_each(|(k, v)| f(k, v))
+    }
+
+    fn entries(&self) -> JsMapIterator<'_, String, i64> {
+        Box::new(self.map.iter())
+    }
+}
+
+#[test]
+fn v1_lookup_on_dyn_scans_and_lookup_type_uses_the_map() {
+    let probe = Probe {
+        map: HashMap::from(pairs()),
+        ..Probe::default()
+    };
+    let map: &dyn JsMap<String, i64> = &probe;
+
+    // version_1 의 get_value::<Q> 는 Q 를 dyn 너머로 넘길 수 없어서 순회한다
+    assert_eq!(version_1::JsMap::get_value(map, "b"), Some(&2));
+    assert_eq!(version_1::JsMap::get_value(map, &String::from("z")), None);
+    assert_eq!((probe.lookups.get(), probe.scans.get()), (0, 2));
+
+    // K::Lookup 으로 찾으면 원래 맵의 get_value 를 쓴다
+    assert_eq!(JsMap::get_value(map, "c"), Some(&3));
+    assert_eq!((probe.lookups.get(), probe.scans.get()), (1, 2));
+}
+
+#[test]
+fn v1_delete_on_dyn_takes_a_borrowed_key() {
+    let mut map: Box<dyn JsMapMut<String, i64>> = Box::new(HashMap::from(pairs()));
+    assert!(version_1::JsMapMut::delete(&mut map, "a"));
+    assert!(!version_1::JsMapMut::delete(&mut map, "a"));
+    assert_eq!(version_1::JsMapMut::size(&map), 2);
+    assert_eq!(version_1::JsMap::get_value(&map, "b"), Some(&2));
+}
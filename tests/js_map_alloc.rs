@@ -0,0 +1,118 @@
+#![cfg(feature = "js_map")]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+use std::rc::Rc;
+use std::sync::Arc;
+use trait_practice::js_map::key::JsBytes;
+use trait_practice::js_map::ordered::OrderedJsMap;
+use trait_practice::js_map::version_2::JsMap;
+use trait_practice::js_map::{version_1, version_3};
+
+// 테스트는 스레드 여러 개에서 돌기 때문에 스레드별로 센다
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCS.with(Cell::get);
+    f();
+    ALLOCS.with(Cell::get) - before
+}
+
+fn maps() -> Vec<Box<dyn JsMap<isize, i64>>> {
+    vec![
+        Box::new((0..1000).map(|k| (k, k as i64)).collect::<Vec<_>>()),
+        Box::new((0..1000).map(|k| (k, k as i64)).collect::<HashMap<_, _>>()),
+        Box::new(
+            (0..1000)
+                .map(|k| (k, k as i64))
+                .collect::<OrderedJsMap<_, _>>(),
+        ),
+    ]
+}
+
+#[test]
+fn for_each_entry_does_not_allocate() {
+    for map in maps() {
+        let mut sum = 0;
+        assert_eq!(allocations(|| map.for_each_entry(&mut |_, v| sum += v)), 0);
+        assert_eq!(sum, (0..1000).sum::<i64>());
+
+        let mut keys = 0;
+        assert_eq!(allocations(|| map.for_each_key(&mut |_| keys += 1)), 0);
+        assert_eq!(keys, 1000);
+    }
+}
+
+#[test]
+fn entries_allocates_once_per_call() {
+    for map in maps() {
+        let mut sum = 0;
+        assert_eq!(allocations(|| sum = map.entries().map(|(_, v)| v).sum()), 1);
+        assert_eq!(sum, (0..1000).sum::<i64>());
+    }
+}
+
+#[test]
+fn try_for_each_entry_stops_on_break() {
+    for map in maps() {
+        let mut seen = 0;
+        let flow = map.try_for_each_entry(&mut |_, _| {
+            seen += 1;
+            if seen == 10 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(flow, ControlFlow::Break(()));
+        assert_eq!(seen, 10);
+    }
+}
+
+#[test]
+fn borrowed_lookups_do_not_allocate() {
+    let boxed: Vec<(Box<str>, i64)> = vec![(Box::from("mu"), 1), (Box::from("ya"), 2)];
+    let owned: HashMap<String, i64> = HashMap::from([(String::from("mu"), 1)]);
+    let rc: OrderedJsMap<Rc<str>, i64> = OrderedJsMap::from_iter([(Rc::from("mu"), 1)]);
+    let arc: Vec<(Arc<str>, i64)> = vec![(Arc::from("mu"), 1)];
+    let bytes: HashMap<JsBytes, i64> = HashMap::from([(JsBytes::from(&b"mu"[..]), 1)]);
+    let dyn_maps: [&dyn JsMap<Box<str>, i64>; 1] = [&boxed];
+
+    let n = allocations(|| {
+        assert_eq!(version_1::JsMap::get_value(&boxed, "ya"), Some(&2));
+        assert_eq!(version_1::JsMap::get_value(&owned, "mu"), Some(&1));
+        assert_eq!(version_1::JsMap::get_value(&rc, "mu"), Some(&1));
+        assert_eq!(version_1::JsMap::get_value(&arc, "mu"), Some(&1));
+        assert_eq!(version_1::JsMap::get_value(&bytes, &b"mu"[..]), Some(&1));
+        assert_eq!(version_1::JsMap::get_value(&bytes, &b"ya"[..]), None);
+
+        assert_eq!(version_3::JsMap::get_value(&owned, "mu"), Some(&1));
+        assert_eq!(version_3::JsMap::get_value(&bytes, &b"mu"[..]), Some(&1));
+
+        assert_eq!(dyn_maps[0].get_value("ya"), Some(&2));
+        assert_eq!(dyn_maps[0].get_value("ho"), None);
+        assert_eq!(JsMap::get_value(&owned, "mu"), Some(&1));
+        assert_eq!(JsMap::get_value(&rc, "mu"), Some(&1));
+        assert_eq!(JsMap::get_value(&bytes, &b"mu"[..]), Some(&1));
+    });
+    assert_eq!(n, 0);
+}
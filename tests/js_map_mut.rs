@@ -0,0 +1,74 @@
+#![cfg(feature = "js_map")]
+
+use std::collections::HashMap;
+use trait_practice::js_map::{version_1, version_2};
+
+fn pairs<const N: usize>(pairs: [(&str, i32); N]) -> [(String, i32); N] {
+    pairs.map(|(k, v)| (k.to_owned(), v))
+}
+
+fn keys_v2(map: &dyn version_2::JsMap<&'static str, i32>) -> Vec<&'static str> {
+    let mut keys = Vec::new();
+    map.for_each_key(&mut |key| keys.push(*key));
+    keys
+}
+
+#[test]
+fn v1_set_keeps_position_of_existing_key() {
+    use version_1::{JsMap, JsMapMut};
+    let mut map = Vec::from(pairs([("a", 1), ("b", 2)]));
+    map.set("a".into(), 10).set("c".into(), 3);
+    let keys: Vec<&String> = map.keys().collect();
+    assert_eq!(keys, ["a", "b", "c"]);
+    assert_eq!(map.get_value("a"), Some(&10));
+    assert_eq!(map.size(), 3);
+}
+
+// version_1 은 dyn 으로 못 쓰므로 제네릭 함수로 맵마다 같은 검사를 돌린다
+fn check_v1_delete_has_clear_size<M>(mut map: M)
+where
+    M: for<'a> version_1::JsMapMut<'a, Key = String, Value = i32>,
+{
+    assert!(map.has("a"));
+    assert!(map.delete("a"));
+    assert!(!map.delete("a"));
+    assert!(!map.has("a"));
+    assert_eq!(map.size(), 1);
+    map.clear();
+    assert_eq!(map.size(), 0);
+    assert!(!map.has("b"));
+}
+
+#[test]
+fn v1_delete_has_clear_size() {
+    check_v1_delete_has_clear_size(Vec::from(pairs([("a", 1), ("b", 2)])));
+    check_v1_delete_has_clear_size(HashMap::from(pairs([("a", 1), ("b", 2)])));
+}
+
+#[test]
+fn v2_set_keeps_position_of_existing_key() {
+    let mut map = vec![("a", 1), ("b", 2)];
+    let dyn_map: &mut dyn version_2::JsMapMut<&str, i32> = &mut map;
+    dyn_map.set("a", 10).set("c", 3);
+    assert_eq!(keys_v2(dyn_map), ["a", "b", "c"]);
+    assert_eq!(dyn_map.get_value("a"), Some(&10));
+    assert_eq!(dyn_map.size(), 3);
+}
+
+#[test]
+fn v2_delete_has_clear_size() {
+    let maps: Vec<Box<dyn version_2::JsMapMut<&str, i32>>> = vec![
+        Box::new(vec![("a", 1), ("b", 2)]),
+        Box::new(HashMap::from([("a", 1), ("b", 2)])),
+    ];
+    for mut map in maps {
+        assert!(map.has("a"));
+        assert!(map.delete("a"));
+        assert!(!map.delete("a"));
+        assert!(!map.has("a"));
+        assert_eq!(map.size(), 1);
+        map.clear();
+        assert_eq!(map.size(), 0);
+        assert!(!map.has("b"));
+    }
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "js_map")]
+
+use std::collections::HashMap;
+use trait_practice::js_map::ordered::OrderedJsMap;
+use trait_practice::js_map::version_3::{FromV1, JsMap, JsMapMut};
+
+fn pairs() -> [(String, i32); 3] {
+    [("c", 3), ("a", 1), ("b", 2)].map(|(k, v)| (k.to_owned(), v))
+}
+
+fn sorted_entries(map: &impl JsMap<Key = String, Value = i32>) -> Vec<(&str, i32)> {
+    let mut entries: Vec<_> = map.entries().map(|(k, v)| (k.as_str(), *v)).collect();
+    entries.sort();
+    entries
+}
+
+// set/delete/has/size/clear 를 구현마다 같은 순서로 확인한다
+fn check_mut<M: JsMapMut<Key = String, Value = i32>>(mut map: M) {
+    assert_eq!(map.size(), 3);
+    assert_eq!(map.get_value("a"), Some(&1));
+    assert!(map.has("b") && !map.has("z"));
+
+    map.set(String::from("a"), 10).set(String::from("d"), 4);
+    assert_eq!(
+        sorted_entries(&map),
+        [("a", 10), ("b", 2), ("c", 3), ("d", 4)]
+    );
+
+    assert!(map.delete("b"));
+    assert!(!map.delete("b"));
+    assert_eq!(map.size(), 3);
+    assert!(!map.has("b"));
+
+    map.clear();
+    assert_eq!(map.size(), 0);
+    assert_eq!(map.entries().count(), 0);
+}
+
+#[test]
+fn every_map_behaves_the_same() {
+    check_mut(Vec::from(pairs()));
+    check_mut(HashMap::from(pairs()));
+    check_mut(FromV1(OrderedJsMap::from_iter(pairs())));
+}
+
+#[test]
+fn from_v1_keeps_insertion_order() {
+    let mut map = FromV1(OrderedJsMap::from_iter(pairs()));
+    map.delete("c");
+    map.set(String::from("a"), 100).set(String::from("c"), 30);
+    let keys: Vec<&str> = map.keys().map(String::as_str).collect();
+    assert_eq!(keys, ["a", "b", "c"]);
+    assert_eq!(map.get_value("a"), Some(&100));
+    assert_eq!(map.0.get_value("c"), Some(&30));
+}
+
+// GAT 라서 lifetime 없이 구조체 필드와 반환값에 쓸 수 있다
+struct Labeled<M: JsMap> {
+    label: &'static str,
+    map: M,
+}
+
+fn largest_value<M: JsMap<Value = i32>>(map: &M) -> Option<(&M::Key, &i32)> {
+    map.entries().max_by_key(|&(_, v)| *v)
+}
+
+#[test]
+fn maps_are_stored_and_borrowed_without_lifetimes() {
+    let labeled = Labeled {
+        label: "scores",
+        map: Vec::from(pairs()),
+    };
+    assert_eq!(labeled.label, "scores");
+    assert_eq!(largest_value(&labeled.map), Some((&String::from("c"), &3)));
+
+    let ordered = Labeled {
+        label: "ordered",
+        map: FromV1(OrderedJsMap::from_iter(pairs())),
+    };
+    assert_eq!(
+        largest_value(&ordered.map).map(|(k, _)| k.as_str()),
+        Some("c")
+    );
+    assert_eq!(largest_value(&Vec::<(String, i32)>::new()), None);
+}
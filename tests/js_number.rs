@@ -0,0 +1,125 @@
+#![cfg(feature = "js_map")]
+
+use proptest::prelude::*;
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use trait_practice::js_map::key::JsNumber;
+use trait_practice::js_map::ordered::OrderedJsMap;
+use trait_practice::js_map::{version_1, version_2};
+
+fn hash(number: JsNumber) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    number.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn same_value_zero() {
+    assert_eq!(JsNumber(f64::NAN), JsNumber(f64::NAN));
+    assert_eq!(JsNumber(f64::NAN), JsNumber(-f64::NAN));
+    assert_eq!(JsNumber(0.0), JsNumber(-0.0));
+    assert_eq!(hash(JsNumber(f64::NAN)), hash(JsNumber(-f64::NAN)));
+    assert_eq!(hash(JsNumber(0.0)), hash(JsNumber(-0.0)));
+    assert_ne!(JsNumber(1.0), JsNumber(-1.0));
+    assert_ne!(JsNumber(f64::NAN), JsNumber(f64::INFINITY));
+}
+
+#[test]
+fn total_order() {
+    let mut numbers = [f64::NAN, 1.0, f64::INFINITY, -0.0, f64::NEG_INFINITY, -1.5].map(JsNumber);
+    numbers.sort();
+    let shown: Vec<String> = numbers.iter().map(ToString::to_string).collect();
+    assert_eq!(shown, ["-Infinity", "-1.5", "0", "1", "Infinity", "NaN"]);
+}
+
+#[test]
+fn js_display() {
+    let cases = [
+        (1.0, "1"),
+        (-2.0, "-2"),
+        (1.5, "1.5"),
+        (-0.0, "0"),
+        (0.1 + 0.2, "0.30000000000000004"),
+        (f64::NAN, "NaN"),
+        (f64::INFINITY, "Infinity"),
+        (f64::NEG_INFINITY, "-Infinity"),
+        (1e21, "1e+21"),
+        (1.5e300, "1.5e+300"),
+        (123456789012345680000.0, "123456789012345680000"),
+        (0.000001, "0.000001"),
+        (1.5e-7, "1.5e-7"),
+        (-5e-324, "-5e-324"),
+    ];
+    for (value, expected) in cases {
+        assert_eq!(JsNumber(value).to_string(), expected, "{value:?}");
+    }
+}
+
+#[test]
+fn number_keys_in_both_versions() {
+    {
+        use version_1::{JsMap, JsMapMut};
+        let mut ordered = OrderedJsMap::new();
+        ordered
+            .set(JsNumber(f64::NAN), "nan")
+            .set(JsNumber(0.0), "+0")
+            .set(JsNumber(-0.0), "-0");
+        assert_eq!(ordered.size(), 2);
+        assert_eq!(ordered.get_value(&JsNumber(-f64::NAN)), Some(&"nan"));
+        assert_eq!(ordered.get_value(&JsNumber(0.0)), Some(&"-0"));
+    }
+
+    let map: HashMap<JsNumber, &str> = HashMap::from([(JsNumber(-0.0), "zero")]);
+    let map: &dyn version_2::JsMap<JsNumber, &str> = &map;
+    assert_eq!(map.get_value(&JsNumber(0.0)), Some(&"zero"));
+    assert_eq!(map.get_value(&JsNumber(f64::NAN)), None);
+
+    let vec = vec![(JsNumber(f64::NAN), 1), (JsNumber(2.0), 2)];
+    assert_eq!(
+        version_2::JsMap::get_value(&vec, &JsNumber(f64::NAN)),
+        Some(&1)
+    );
+}
+
+proptest! {
+    #[test]
+    fn eq_hash_and_ord_agree(a in any::<f64>(), b in any::<f64>()) {
+        let (a, b) = (JsNumber(a), JsNumber(b));
+        prop_assert_eq!(a == b, a.cmp(&b) == Ordering::Equal);
+        prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+        if a == b {
+            prop_assert_eq!(hash(a), hash(b));
+        }
+        let js_equal = a.value() == b.value() || (a.value().is_nan() && b.value().is_nan());
+        prop_assert_eq!(a == b, js_equal);
+    }
+
+    #[test]
+    fn display_round_trips(value in any::<f64>()) {
+        let shown = JsNumber(value).to_string();
+        let parsed: f64 = match shown.as_str() {
+            "Infinity" => f64::INFINITY,
+            "-Infinity" => f64::NEG_INFINITY,
+            text => text.parse().unwrap(),
+        };
+        prop_assert_eq!(JsNumber(parsed), JsNumber(value));
+        prop_assert!(!shown.ends_with(".0"));
+    }
+
+    #[test]
+    fn btree_and_hash_maps_agree(keys in prop::collection::vec(
+        prop_oneof![Just(f64::NAN), Just(-f64::NAN), Just(0.0), Just(-0.0), any::<f64>()],
+        0..32,
+    )) {
+        let hashed: HashMap<JsNumber, usize> =
+            keys.iter().enumerate().map(|(i, &k)| (JsNumber(k), i)).collect();
+        let sorted: BTreeMap<JsNumber, usize> =
+            keys.iter().enumerate().map(|(i, &k)| (JsNumber(k), i)).collect();
+        prop_assert_eq!(hashed.len(), sorted.len());
+        for (key, value) in &sorted {
+            prop_assert_eq!(hashed.get(key), Some(value));
+        }
+    }
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "object")]
+
+use chrono::{TimeZone, Utc};
+use serde_json::{json, Value};
+use trait_practice::object::json::{to_json, to_json_array};
+use trait_practice::object::reward::CouponState;
+use trait_practice::object::version_1::{Avatar, Coupon, Gem, Item, Quest, RewardObj, Unknown};
+
+fn parse(text: &str) -> Value {
+    serde_json::from_str(text).unwrap_or_else(|err| panic!("invalid json {text}: {err}"))
+}
+
+#[test]
+fn every_kind_is_exported() {
+    let at = Utc.timestamp_opt(1_688_169_600, 0).unwrap();
+    let rewards: Vec<RewardObj> = vec![
+        Box::new(Gem {
+            currency: String::from("XP"),
+            delta: 0,
+            min: Some(100),
+            max: Some(500),
+            name: None,
+            shelf_life: None,
+            is_hidden: true,
+        }),
+        Box::new(Coupon {
+            coupon_type: String::from("DISCOUNT"),
+            code: String::from("ABCD-1234"),
+            name: None,
+            state: CouponState::Redeemed(at),
+            shelf_life: Some(at),
+            delta: 1,
+            is_hidden: false,
+        }),
+        Box::new(Unknown),
+    ];
+    assert_eq!(
+        parse(&to_json_array(&rewards)),
+        json!([
+            {
+                "kind": "gem",
+                "currency": "XP",
+                "delta": 0,
+                "min": 100,
+                "max": 500,
+                "name": null,
+                "shelfLife": null,
+                "isHidden": true
+            },
+            {
+                "kind": "coupon",
+                "couponType": "DISCOUNT",
+                "code": "ABCD-1234",
+                "name": null,
+                "redeemedAt": "2023-07-01T00:00:00Z",
+                "shelfLife": "2023-07-01T00:00:00Z",
+                "delta": 1,
+                "isHidden": false
+            },
+            {"kind": "unknown"}
+        ])
+    );
+}
+
+#[test]
+fn strings_are_escaped() {
+    let name = "\"Boss\" \\ line\nbreak\u{1}";
+    let quest = Quest {
+        quest_id: String::from("Q1"),
+        name: String::from(name),
+        required: 3,
+        url: None,
+        code: None,
+        shelf_life: None,
+        is_hidden: false,
+    };
+    assert_eq!(parse(&to_json(&quest))["name"], name);
+}
+
+#[test]
+fn optional_fields_are_exported() {
+    let at = Utc.timestamp_opt(1_688_169_600, 0).unwrap();
+    let rewards: Vec<RewardObj> = vec![
+        Box::new(Item {
+            item_type: String::from("POUCH"),
+            url: String::from("https://static.playio.club/pouch.png"),
+            name: String::from("Gem Pouch"),
+            shelf_life: None,
+            delta: 2,
+            code: Some(String::from("P-1")),
+            is_hidden: false,
+        }),
+        Box::new(Avatar {
+            cosmetic_id: String::from("HAT"),
+            url: String::from("https://static.playio.club/hat.png"),
+            name: Some(String::from("Party Hat")),
+            delta: 1,
+            is_hidden: false,
+        }),
+        Box::new(Quest {
+            quest_id: String::from("Q1"),
+            name: String::from("Daily"),
+            required: 3,
+            url: Some(String::from("https://playio.club/q1")),
+            code: Some(String::from("Q-1")),
+            shelf_life: Some(at),
+            is_hidden: false,
+        }),
+    ];
+    let json = parse(&to_json_array(&rewards));
+    assert_eq!(json[0]["code"], "P-1");
+    assert_eq!(
+        (&json[1]["name"], &json[1]["delta"]),
+        (&json!("Party Hat"), &json!(1))
+    );
+    assert_eq!(
+        (&json[2]["url"], &json[2]["code"], &json[2]["shelfLife"]),
+        (
+            &json!("https://playio.club/q1"),
+            &json!("Q-1"),
+            &json!("2023-07-01T00:00:00Z")
+        )
+    );
+}
@@ -0,0 +1,190 @@
+#![cfg(feature = "object")]
+
+mod common;
+
+use common::asset;
+use std::ops::RangeInclusive;
+use trait_practice::object::error::RewardError;
+use trait_practice::object::loot::{Chance, LootDrop, LootTable, PityCounters};
+use trait_practice::object::registry::RewardRegistry;
+use trait_practice::object::reward::RewardDto;
+use trait_practice::object::rng::SeededRng;
+
+// XP 는 항상, 나머지는 NOTHING 6 : GOLD 3 : (RARE 테이블) 1
+fn chest() -> LootTable {
+    let rare = LootTable::new("rare", "Rare")
+        .with_entry(1, LootDrop::reward(asset("RUBY", 0), 1..=1).unwrap())
+        .with_entry(3, LootDrop::reward(asset("PEARL", 0), 2..=5).unwrap())
+        .with_entry(0, LootDrop::reward(asset("NEVER", 0), 1..=1).unwrap());
+    LootTable::new("chest", "Chest")
+        .with_guaranteed(LootDrop::reward(asset("XP", 0), 100..=300).unwrap())
+        .with_entry(6, LootDrop::Nothing)
+        .with_entry(3, LootDrop::reward(asset("GOLD", 0), 1..=10).unwrap())
+        .with_entry(1, LootDrop::table(rare))
+}
+
+fn currencies(drops: &[RewardDto]) -> Vec<&str> {
+    drops.iter().map(|dto| dto._type.as_str()).collect()
+}
+
+#[test]
+fn drop_rates_are_exact() {
+    let table = chest();
+    let rates: Vec<_> = table
+        .drop_rates()
+        .unwrap()
+        .into_iter()
+        .map(|rate| {
+            (
+                rate.path.join(">"),
+                rate.template._type.clone(),
+                rate.chance,
+            )
+        })
+        .collect();
+    assert_eq!(
+        rates,
+        [
+            ("Chest".into(), "XP".into(), Chance::ONE),
+            ("Chest".into(), "GOLD".into(), Chance::new(3, 10)),
+            ("Chest>Rare".into(), "RUBY".into(), Chance::new(1, 40)),
+            ("Chest>Rare".into(), "PEARL".into(), Chance::new(3, 40)),
+        ]
+    );
+}
+
+#[test]
+fn rolls_are_seeded_and_follow_the_rates() {
+    let table = chest();
+    let roll_many = |seed| {
+        let mut rng = SeededRng::new(seed);
+        let mut pity = PityCounters::new();
+        (0..20_000)
+            .map(|_| table.roll(&mut rng, &mut pity))
+            .collect::<Vec<_>>()
+    };
+    let rolls = roll_many(1);
+    assert_eq!(rolls, roll_many(1));
+
+    let count = |currency| {
+        rolls
+            .iter()
+            .filter(|drops| currencies(drops).contains(&currency))
+            .count() as f64
+            / rolls.len() as f64
+    };
+    assert_eq!(count("XP"), 1.0);
+    assert!((count("GOLD") - 0.3).abs() < 0.02);
+    assert!((count("PEARL") - 0.075).abs() < 0.01);
+    assert_eq!(count("NEVER"), 0.0);
+
+    for drops in &rolls {
+        assert!(drops[0].delta >= 100 && drops[0].delta <= 300);
+        assert!(drops.len() <= 2);
+    }
+}
+
+#[test]
+fn pity_forces_the_entry_and_resets() {
+    let table = LootTable::new("banner", "Banner")
+        .with_entry(1_000_000, LootDrop::Nothing)
+        .with_entry(1, LootDrop::reward(asset("LEGEND", 0), 1..=1).unwrap())
+        .with_pity(1, 10)
+        .unwrap();
+    let mut rng = SeededRng::new(3);
+    let mut pity = PityCounters::new();
+    for round in 0..3 {
+        for _ in 0..9 {
+            assert!(table.roll(&mut rng, &mut pity).is_empty(), "round {round}");
+        }
+        assert_eq!(pity.misses("banner"), 9);
+        assert_eq!(currencies(&table.roll(&mut rng, &mut pity)), ["LEGEND"]);
+        assert_eq!(pity.misses("banner"), 0);
+    }
+}
+
+#[test]
+fn drops_go_through_the_factory() {
+    let registry = RewardRegistry::builtin();
+    let mut rng = SeededRng::new(9);
+    let mut pity = PityCounters::new();
+    for _ in 0..100 {
+        for dto in chest().roll(&mut rng, &mut pity) {
+            let reward = registry.build(dto.clone()).unwrap();
+            assert_eq!(reward.to_dto(), Some(dto));
+        }
+    }
+}
+
+#[test]
+fn pity_is_keyed_by_table_id() {
+    let banner = |id: &str, name: &str| {
+        LootTable::new(id, name)
+            .with_entry(1_000_000, LootDrop::Nothing)
+            .with_entry(1, LootDrop::reward(asset("LEGEND", 0), 1..=1).unwrap())
+            .with_pity(1, 10)
+            .unwrap()
+    };
+    let mut rng = SeededRng::new(3);
+    let mut pity = PityCounters::new();
+    for _ in 0..5 {
+        banner("summer", "Banner").roll(&mut rng, &mut pity);
+    }
+    // 이름이 같아도 id 가 다르면 카운터를 나누지 않는다
+    banner("winter", "Banner").roll(&mut rng, &mut pity);
+    assert_eq!((pity.misses("summer"), pity.misses("winter")), (5, 1));
+    assert_eq!(pity.misses("Banner"), 0);
+
+    // 표시 이름을 바꿔도 id 가 같으면 카운터가 이어진다
+    for _ in 0..4 {
+        banner("summer", "Summer Banner").roll(&mut rng, &mut pity);
+    }
+    assert_eq!(pity.misses("summer"), 9);
+    let drops = banner("summer", "Summer Banner").roll(&mut rng, &mut pity);
+    assert_eq!(currencies(&drops), ["LEGEND"]);
+}
+
+#[test]
+fn empty_quantity_is_rejected() {
+    assert_eq!(
+        LootDrop::reward(asset("GOLD", 0), RangeInclusive::new(5, 1)).err(),
+        Some(RewardError::InvalidRange { min: 5, max: 1 })
+    );
+}
+
+#[test]
+fn pity_must_point_at_an_entry() {
+    let table = LootTable::new("banner", "Banner").with_entry(1, LootDrop::Nothing);
+    assert_eq!(
+        table.with_pity(1, 10).err(),
+        Some(RewardError::UnknownPityEntry {
+            table: String::from("banner"),
+            entry: 1
+        })
+    );
+}
+
+#[test]
+fn deeply_nested_large_weights_report_overflow() {
+    // 층마다 분모가 약 2^33 씩 커진다. 4층이면 u128 을 넘는다
+    let table = (0..4).fold(
+        LootTable::new("core", "Core")
+            .with_entry(1, LootDrop::reward(asset("GOLD", 0), 1..=1).unwrap()),
+        |inner, depth| {
+            LootTable::new(format!("layer{depth}"), "Layer")
+                .with_entry(u32::MAX, LootDrop::table(inner))
+                .with_entry(u32::MAX - 1, LootDrop::Nothing)
+        },
+    );
+    assert!(matches!(
+        table.drop_rates().err(),
+        Some(RewardError::ChanceOverflow { .. })
+    ));
+
+    let max = Chance::new(u128::MAX - 1, u128::MAX);
+    assert_eq!(max.checked_mul(max), None);
+    assert_eq!(
+        Chance::new(2, 3).checked_mul(Chance::new(3, 4)),
+        Some(Chance::new(1, 2))
+    );
+}
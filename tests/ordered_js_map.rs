@@ -0,0 +1,60 @@
+#![cfg(feature = "js_map")]
+
+use trait_practice::js_map::ordered::OrderedJsMap;
+use trait_practice::js_map::version_2::{JsMap, JsMapMut};
+
+fn keys(map: &OrderedJsMap<&'static str, i32>) -> Vec<&'static str> {
+    let mut keys = Vec::new();
+    map.for_each_key(&mut |key| keys.push(*key));
+    keys
+}
+
+#[test]
+fn iterates_in_insertion_order() {
+    let mut map = OrderedJsMap::new();
+    map.set("c", 3).set("a", 1).set("b", 2);
+    assert_eq!(keys(&map), ["c", "a", "b"]);
+    let values: Vec<i32> = map.entries().map(|(_, v)| *v).collect();
+    assert_eq!(values, [3, 1, 2]);
+}
+
+#[test]
+fn reinsert_after_delete_moves_key_to_end() {
+    let mut map = OrderedJsMap::from_iter([("a", 1), ("b", 2), ("c", 3)]);
+    assert!(map.delete("a"));
+    map.set("a", 10);
+    assert_eq!(keys(&map), ["b", "c", "a"]);
+    assert_eq!(map.get_value("a"), Some(&10));
+}
+
+#[test]
+fn set_on_existing_key_keeps_position() {
+    let mut map = OrderedJsMap::from_iter([("a", 1), ("b", 2), ("c", 3)]);
+    map.set("a", 10).set("b", 20);
+    assert_eq!(keys(&map), ["a", "b", "c"]);
+    assert_eq!(map.get_value("b"), Some(&20));
+    assert_eq!(map.size(), 3);
+}
+
+#[test]
+fn index_stays_consistent_after_compaction() {
+    const KEYS: [&str; 10] = ["k0", "k1", "k2", "k3", "k4", "k5", "k6", "k7", "k8", "k9"];
+    let mut map: OrderedJsMap<&str, i32> = KEYS.iter().copied().zip(0..).collect();
+    // 10개 중 6개를 지우면 빈 자리가 절반을 넘어 정리가 한 번 일어난다
+    for key in ["k0", "k2", "k3", "k5", "k6", "k8"] {
+        assert!(map.delete(key));
+    }
+    assert_eq!(keys(&map), ["k1", "k4", "k7", "k9"]);
+    for (key, value) in [("k1", 1), ("k4", 4), ("k7", 7), ("k9", 9)] {
+        assert_eq!(map.get_value(key), Some(&value));
+    }
+    assert_eq!(map.get_value("k0"), None);
+
+    map.set("k4", 40).set("k0", 0);
+    assert_eq!(keys(&map), ["k1", "k4", "k7", "k9", "k0"]);
+    assert!(map.delete("k7"));
+    assert_eq!(keys(&map), ["k1", "k4", "k9", "k0"]);
+    assert_eq!(map.get_value("k4"), Some(&40));
+    assert_eq!(map.get_value("k9"), Some(&9));
+    assert_eq!(map.size(), 4);
+}
@@ -0,0 +1,173 @@
+#![cfg(feature = "object")]
+
+mod common;
+
+use common::gem_reward;
+use trait_practice::object::inventory::{grant, GrantError, Granted, Inventory};
+use trait_practice::object::version_1::{Avatar, Gem, Reward, RewardBundle, RewardObj};
+use trait_practice::object::version_2::{self, RewardDisplay};
+
+fn avatar() -> Avatar {
+    Avatar {
+        cosmetic_id: String::from("FRAME_GOLD"),
+        url: String::from("https://static.playio.club/__asset/frame_gold.png"),
+        name: None,
+        delta: 1,
+        is_hidden: false,
+    }
+}
+
+// Spring Chest = 1000 XP + (Star Pack = 50 STAR + 500 XP + 숨김 Avatar)
+fn chest() -> RewardBundle {
+    let hidden = Avatar {
+        is_hidden: true,
+        ..avatar()
+    };
+    let star_pack = RewardBundle::new(
+        "Star Pack",
+        vec![
+            Box::new(gem_reward("STAR", 50)),
+            Box::new(gem_reward("XP", 500)),
+            Box::new(hidden),
+        ],
+    );
+    RewardBundle::new(
+        "Spring Chest",
+        vec![Box::new(gem_reward("XP", 1000)), Box::new(star_pack)],
+    )
+}
+
+fn fmt_string(reward: &RewardObj) -> String {
+    reward.try_display().unwrap().fmt_string.into()
+}
+
+#[test]
+fn nested_bundle_displays_as_aggregate() {
+    let chest: RewardObj = Box::new(chest());
+    assert_eq!(
+        fmt_string(&chest),
+        "Spring Chest (1000 Gems + Star Pack (50 Gems + 500 Gems))"
+    );
+    assert_eq!(
+        version_2::Reward::from(chest).fmt_string(),
+        "Spring Chest (1000 Gems + Star Pack (50 Gems + 500 Gems))"
+    );
+}
+
+#[test]
+fn flatten_and_merge_sum_identical_currencies() {
+    assert_eq!(chest().flatten().len(), 4);
+
+    let merged: RewardObj = Box::new(chest().merge());
+    assert_eq!(fmt_string(&merged), "Spring Chest (1500 Gems + 50 Gems)");
+
+    let v2 = version_2::Reward::from(Box::new(chest()) as RewardObj).merge();
+    assert_eq!(v2.fmt_string(), "Spring Chest (1500 Gems + 50 Gems)");
+}
+
+#[test]
+fn merge_keeps_unrolled_and_overflowing_gems_apart() {
+    let ranged = Gem {
+        min: Some(1),
+        max: Some(10),
+        ..gem_reward("XP", 0)
+    };
+    let bundle = RewardBundle::new(
+        "Edge",
+        vec![
+            Box::new(gem_reward("XP", u32::MAX)),
+            Box::new(gem_reward("XP", 1)),
+            Box::new(ranged),
+            Box::new(avatar()),
+        ],
+    );
+    assert_eq!(bundle.merge().rewards.len(), 4);
+}
+
+#[test]
+fn bundle_is_granted_atomically() {
+    let mut inventory = Inventory::new();
+    let receipt = grant(&mut inventory, &(Box::new(chest()) as RewardObj)).unwrap();
+    assert_eq!(inventory.balance("XP"), 1500);
+    assert_eq!(inventory.balance("STAR"), 50);
+    assert!(matches!(
+        &receipt.granted[..],
+        [Granted::Bundle { name, granted }] if name == "Spring Chest" && granted.len() == 2
+    ));
+
+    // 뒤쪽 보상이 실패하면 앞에서 넣은 XP 도 반영되지 않는다
+    let unrolled = Gem {
+        min: Some(1),
+        max: Some(10),
+        ..gem_reward("STAR", 0)
+    };
+    let broken: RewardObj = Box::new(RewardBundle::new(
+        "Broken",
+        vec![Box::new(gem_reward("XP", 100)), Box::new(unrolled)],
+    ));
+    let before = inventory.clone();
+    assert_eq!(
+        grant(&mut inventory, &broken).unwrap_err(),
+        GrantError::Unrolled {
+            currency: String::from("STAR")
+        }
+    );
+    assert_eq!(inventory, before);
+}
+
+// Spring Chest = 1000 XP + 숨김 (Secret Pack = 500 XP)
+fn chest_with_secret() -> RewardBundle {
+    let secret = RewardBundle {
+        is_hidden: true,
+        ..RewardBundle::new("Secret Pack", vec![Box::new(gem_reward("XP", 500))])
+    };
+    RewardBundle::new(
+        "Spring Chest",
+        vec![Box::new(gem_reward("XP", 1000)), Box::new(secret)],
+    )
+}
+
+#[test]
+fn hidden_nested_bundle_is_not_flattened_or_merged() {
+    let flat = chest_with_secret().flatten();
+    assert_eq!(flat.len(), 2);
+    let secret = flat[1].downcast_ref::<RewardBundle>().unwrap();
+    assert!(secret.is_hidden);
+
+    let merged: RewardObj = Box::new(chest_with_secret().merge());
+    assert_eq!(fmt_string(&merged), "Spring Chest (1000 Gems)");
+
+    let v2 = || version_2::Reward::from(Box::new(chest_with_secret()) as RewardObj);
+    assert!(matches!(
+        &v2().flatten()[..],
+        [
+            version_2::Reward::Gem { .. },
+            version_2::Reward::Bundle {
+                is_hidden: true,
+                ..
+            }
+        ]
+    ));
+    assert_eq!(v2().merge().fmt_string(), "Spring Chest (1000 Gems)");
+}
+
+#[test]
+fn bundle_exports_its_rewards_as_dtos() {
+    let chest = chest_with_secret();
+    assert!(chest.to_dto().is_none());
+    let dtos = chest.to_dtos();
+    assert_eq!(
+        dtos.iter()
+            .map(|dto| (dto.delta, dto.is_hidden))
+            .collect::<Vec<_>>(),
+        [(1000, false), (500, true)]
+    );
+
+    let v2 = version_2::Reward::from(Box::new(chest_with_secret()) as RewardObj);
+    assert!(v2.to_dto().is_none());
+    assert_eq!(v2.to_dtos(), dtos);
+
+    // 숨김 DTO 로 다시 만들면 숨김 보상이 된다
+    let secret = version_2::Reward::try_from(dtos[1].clone()).unwrap();
+    assert!(secret.is_hidden());
+}
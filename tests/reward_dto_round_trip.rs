@@ -0,0 +1,102 @@
+#![cfg(feature = "object")]
+
+mod common;
+
+use common::{any_dto, valid_dto};
+use proptest::prelude::*;
+use trait_practice::object::error::RewardError;
+use trait_practice::object::registry::RewardRegistry;
+use trait_practice::object::reward::{RewardDto, RewardGroup};
+use trait_practice::object::version_2;
+
+// 아무 DTO 나 만들고, 두 모델이 받아들인 것만 남긴다
+fn built_dto() -> impl Strategy<Value = RewardDto> {
+    any_dto().prop_filter("rejected by version_2", |dto| {
+        version_2::Reward::try_from(dto.clone()).is_ok()
+    })
+}
+
+proptest! {
+    #[test]
+    fn v1_dto_reward_dto_is_lossless(dto in valid_dto()) {
+        let reward = RewardRegistry::builtin().build(dto.clone()).unwrap();
+        prop_assert_eq!(reward.to_dto(), Some(dto));
+    }
+
+    #[test]
+    fn v2_dto_reward_dto_is_lossless(dto in valid_dto()) {
+        let reward = version_2::Reward::try_from(dto.clone()).unwrap();
+        prop_assert_eq!(reward.to_dto(), Some(dto));
+    }
+
+    // 받아들인 DTO 는 어떤 필드도 잃지 않는다
+    #[test]
+    fn accepted_dto_round_trips_in_both_models(dto in built_dto()) {
+        let v1 = RewardRegistry::builtin().build(dto.clone()).unwrap();
+        prop_assert_eq!(v1.to_dto(), Some(dto.clone()));
+        let v2 = version_2::Reward::try_from(dto.clone()).unwrap();
+        prop_assert_eq!(v2.to_dto(), Some(dto));
+    }
+}
+
+#[test]
+fn unknown_reward_has_no_dto() {
+    assert_eq!(version_2::Reward::Unknown.to_dto(), None);
+}
+
+#[test]
+fn fields_a_reward_cannot_hold_are_rejected() {
+    let text = || Some(String::from("X"));
+    let cases = [
+        (
+            RewardDto {
+                code: text(),
+                ..RewardDto::empty(RewardGroup::ASSET)
+            },
+            RewardError::UnexpectedField("code"),
+        ),
+        (
+            RewardDto {
+                name: text(),
+                url: text(),
+                min: Some(1),
+                ..RewardDto::empty(RewardGroup::ITEM)
+            },
+            RewardError::UnexpectedField("min"),
+        ),
+        (
+            RewardDto {
+                url: text(),
+                shelf_life: Some(chrono::DateTime::default()),
+                ..RewardDto::empty(RewardGroup::AVATAR)
+            },
+            RewardError::UnexpectedField("shelf_life"),
+        ),
+        (
+            RewardDto {
+                code: text(),
+                url: text(),
+                ..RewardDto::empty(RewardGroup::COUPON)
+            },
+            RewardError::UnexpectedField("url"),
+        ),
+        (
+            RewardDto {
+                name: text(),
+                max: Some(1),
+                ..RewardDto::empty(RewardGroup::QUEST)
+            },
+            RewardError::UnexpectedField("max"),
+        ),
+    ];
+    for (dto, expected) in cases {
+        let v1 = RewardRegistry::builtin().build(dto.clone()).err();
+        let v2 = version_2::Reward::try_from(dto).err();
+        assert_eq!(v1.as_ref(), Some(&expected));
+        assert_eq!(v2.as_ref(), Some(&expected));
+    }
+    assert_eq!(
+        RewardError::UnexpectedField("url").to_string(),
+        "unexpected field `url`"
+    );
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "object")]
+
+use trait_practice::object::error::RewardError;
+use trait_practice::object::reward::{RewardDto, RewardGroup};
+use trait_practice::object::{version_1, version_2};
+
+// 보상 타입들은 Debug 가 없어서 unwrap_err 대신 쓴다
+fn error<T>(result: Result<T, RewardError>) -> RewardError {
+    result.err().expect("conversion should fail")
+}
+
+fn missing_item() -> RewardDto {
+    RewardDto {
+        _type: String::from("PASS"),
+        ..RewardDto::empty(RewardGroup::ITEM)
+    }
+}
+
+#[test]
+fn every_invalid_field_is_reported() {
+    let expected = RewardError::Multiple(vec![
+        RewardError::MissingField("url"),
+        RewardError::MissingField("name"),
+    ]);
+    let v1 = error(version_1::Item::try_from(missing_item()));
+    let v2 = error(version_2::Reward::try_from(missing_item()));
+    assert_eq!(v1, expected);
+    assert_eq!(v2, expected);
+    assert_eq!(v1.errors().len(), 2);
+    assert_eq!(v1.to_string(), "missing field `url`; missing field `name`");
+}
+
+#[test]
+fn single_error_is_not_wrapped() {
+    let dto = RewardDto {
+        name: Some(String::from("Pass")),
+        ..missing_item()
+    };
+    let expected = RewardError::MissingField("url");
+    assert_eq!(error(version_1::Item::try_from(dto.clone())), expected);
+    assert_eq!(error(version_2::Reward::try_from(dto)), expected);
+
+    let dto = RewardDto {
+        min: Some(10),
+        max: Some(1),
+        ..RewardDto::empty(RewardGroup::ASSET)
+    };
+    let expected = RewardError::InvalidRange { min: 10, max: 1 };
+    assert_eq!(error(version_1::Gem::try_from(dto.clone())), expected);
+    assert_eq!(error(version_2::Reward::try_from(dto)), expected);
+}
+
+#[test]
+fn check_collects_in_order() {
+    assert_eq!(RewardError::check([None, None]), Ok(()));
+    let err = RewardError::check([
+        RewardError::range(Some(3), Some(2)),
+        None,
+        RewardError::missing::<String>("code", &None),
+    ])
+    .unwrap_err();
+    assert_eq!(
+        err.errors(),
+        [
+            &RewardError::InvalidRange { min: 3, max: 2 },
+            &RewardError::MissingField("code"),
+        ]
+    );
+}
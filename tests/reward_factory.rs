@@ -0,0 +1,75 @@
+#![cfg(feature = "object")]
+
+use std::cell::RefCell;
+use trait_practice::object::error::{ErrorCollector, RewardError};
+use trait_practice::object::reward::{RewardDto, RewardGroup};
+use trait_practice::object::version_1::{
+    Gem, Item, RewardFactory, RewardFactorySafe, RewardFactoryUnsafe, Unknown,
+};
+
+fn item(url: Option<&str>) -> RewardDto {
+    RewardDto {
+        _type: String::from("PASS"),
+        name: Some(String::from("Season Pass")),
+        url: url.map(String::from),
+        delta: 1,
+        ..RewardDto::empty(RewardGroup::ITEM)
+    }
+}
+
+#[test]
+fn safe_factory_reports_errors_to_collector() {
+    let collector = ErrorCollector::default();
+    let factory = RewardFactorySafe::new(&collector);
+
+    let reward = factory.gen(item(None));
+    assert!(reward.is::<Unknown>());
+    let unknown_group = RewardDto::empty(RewardGroup::Unknown(String::from("BADGE")));
+    assert!(factory.gen(unknown_group).is::<Unknown>());
+
+    assert_eq!(
+        collector.take(),
+        [
+            RewardError::MissingField("url"),
+            RewardError::UnknownGroup(String::from("BADGE")),
+        ]
+    );
+    // take 는 비운다
+    assert!(collector.take().is_empty());
+}
+
+#[test]
+fn safe_factory_does_not_report_valid_rewards() {
+    let collector = ErrorCollector::default();
+    let factory = RewardFactorySafe::new(&collector);
+    let reward = factory.gen(item(Some("https://static.playio.club/pass.png")));
+    assert!(reward.is::<Item>());
+    assert!(factory
+        .gen(RewardDto::empty(RewardGroup::ASSET))
+        .is::<Gem>());
+    assert!(collector.take().is_empty());
+}
+
+#[test]
+fn closures_are_sinks() {
+    let reported = RefCell::new(Vec::new());
+    let factory =
+        RewardFactorySafe::new(|err: &RewardError| reported.borrow_mut().push(err.to_string()));
+    assert!(factory.gen(item(None)).is::<Unknown>());
+    assert_eq!(reported.into_inner(), ["missing field `url`"]);
+}
+
+#[test]
+fn try_gen_returns_the_same_error() {
+    let factory = RewardFactoryUnsafe::default();
+    assert_eq!(
+        factory.try_gen(item(None)).err(),
+        Some(RewardError::MissingField("url"))
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid reward: missing field `url`")]
+fn unsafe_factory_panics() {
+    RewardFactoryUnsafe::default().gen(item(None));
+}
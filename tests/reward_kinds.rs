@@ -0,0 +1,142 @@
+#![cfg(feature = "object")]
+
+use chrono::{TimeZone, Utc};
+use trait_practice::object::clock::{Clock, ManualClock};
+use trait_practice::object::error::RewardError;
+use trait_practice::object::registry::RewardRegistry;
+use trait_practice::object::reward::{CouponState, RewardDto, RewardGroup};
+use trait_practice::object::version_1::{self, Avatar, Coupon, Quest, Reward};
+use trait_practice::object::version_2::{self, RewardDisplay};
+
+fn avatar() -> RewardDto {
+    RewardDto {
+        _type: String::from("FRAME_GOLD"),
+        url: Some(String::from("https://static.playio.club/frame_gold.png")),
+        ..RewardDto::empty(RewardGroup::AVATAR)
+    }
+}
+
+fn coupon(name: Option<&str>) -> RewardDto {
+    RewardDto {
+        _type: String::from("WELCOME"),
+        code: Some(String::from("PLAYIO-2023")),
+        name: name.map(String::from),
+        ..RewardDto::empty(RewardGroup::COUPON)
+    }
+}
+
+fn quest() -> RewardDto {
+    RewardDto {
+        _type: String::from("DAILY_LOGIN"),
+        name: Some(String::from("Daily Login")),
+        delta: 7,
+        ..RewardDto::empty(RewardGroup::QUEST)
+    }
+}
+
+// v1 은 레지스트리로 만들고, v2 는 TryFrom 으로 만들어서 (fmt_string, image) 를 비교한다
+fn shown(dto: RewardDto) -> [(String, String); 2] {
+    let v1 = RewardRegistry::builtin().build(dto.clone()).unwrap();
+    let v1 = v1.try_display().unwrap();
+    let v2 = version_2::Reward::try_from(dto).unwrap();
+    [
+        (v1.fmt_string.into(), v1.image.into()),
+        (v2.fmt_string(), v2.image().to_owned()),
+    ]
+}
+
+#[test]
+fn avatar_uses_cosmetic_id_and_url() {
+    let avatar_v1 = Avatar::try_from(avatar()).unwrap();
+    assert_eq!(avatar_v1.cosmetic_id, "FRAME_GOLD");
+    for (fmt_string, image) in shown(avatar()) {
+        assert_eq!(fmt_string, "FRAME_GOLD Avatar");
+        assert_eq!(image, "https://static.playio.club/frame_gold.png");
+    }
+}
+
+#[test]
+fn coupon_label_and_redemption() {
+    for (fmt_string, _) in shown(coupon(Some("Welcome Pack"))) {
+        assert_eq!(fmt_string, "Welcome Pack Coupon");
+    }
+    // 이름이 없으면 코드를 보여 준다
+    for (fmt_string, image) in shown(coupon(None)) {
+        assert_eq!(fmt_string, "PLAYIO-2023 Coupon");
+        assert_eq!(image, "Coupon Image");
+    }
+
+    let clock = ManualClock::new(Utc.with_ymd_and_hms(2023, 7, 1, 0, 0, 0).unwrap());
+    let mut v1 = Coupon::try_from(coupon(None)).unwrap();
+    assert!(v1.redeem(&clock));
+    assert!(!v1.redeem(&clock));
+    assert_eq!(v1.state, CouponState::Redeemed(clock.now()));
+    assert_eq!(
+        &*v1.try_display().unwrap().fmt_string,
+        "PLAYIO-2023 Coupon (redeemed)"
+    );
+
+    let mut v2 = version_2::Reward::try_from(coupon(None)).unwrap();
+    assert!(v2.redeem(&clock));
+    assert!(!v2.redeem(&clock));
+    assert_eq!(v2.fmt_string(), "PLAYIO-2023 Coupon (redeemed)");
+}
+
+#[test]
+fn quest_shows_required_count() {
+    let quest_v1 = Quest::try_from(quest()).unwrap();
+    assert_eq!(
+        (quest_v1.quest_id.as_str(), quest_v1.required),
+        ("DAILY_LOGIN", 7)
+    );
+    for (fmt_string, image) in shown(quest()) {
+        assert_eq!(fmt_string, "Daily Login Quest x7");
+        assert_eq!(image, "Quest Image");
+    }
+}
+
+#[test]
+fn required_fields_are_checked_in_both_models() {
+    let cases = [
+        (
+            RewardDto {
+                url: None,
+                ..avatar()
+            },
+            RewardError::MissingField("url"),
+        ),
+        (
+            RewardDto {
+                code: None,
+                ..coupon(None)
+            },
+            RewardError::MissingField("code"),
+        ),
+        (
+            RewardDto {
+                name: None,
+                ..quest()
+            },
+            RewardError::MissingField("name"),
+        ),
+    ];
+    for (dto, expected) in cases {
+        let v1 = RewardRegistry::builtin().build(dto.clone()).err();
+        let v2 = version_2::Reward::try_from(dto).err();
+        assert_eq!(v1.as_ref(), Some(&expected));
+        assert_eq!(v2.as_ref(), Some(&expected));
+    }
+}
+
+#[test]
+fn hidden_rewards_are_not_displayed() {
+    for dto in [avatar(), coupon(None), quest()] {
+        let dto = RewardDto {
+            is_hidden: true,
+            ..dto
+        };
+        let v1: version_1::RewardObj = RewardRegistry::builtin().build(dto.clone()).unwrap();
+        assert!(v1.try_display().is_none());
+        assert!(version_2::Reward::try_from(dto).unwrap().is_hidden());
+    }
+}
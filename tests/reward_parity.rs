@@ -0,0 +1,74 @@
+#![cfg(feature = "object")]
+
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::{any_dto, valid_dto};
+use proptest::prelude::*;
+use trait_practice::object::clock::ManualClock;
+use trait_practice::object::sweeper::Expirable;
+use trait_practice::object::version_1::{RewardFactory, RewardFactoryUnsafe, RewardObj, ShelfLife};
+use trait_practice::object::version_2::{self, RewardDisplay, TimeLimited};
+
+fn clock() -> impl Strategy<Value = ManualClock> {
+    (0i64..4_102_444_800).prop_map(|secs| ManualClock::new(Utc.timestamp_opt(secs, 0).unwrap()))
+}
+
+// v1 은 시계를 주입한 팩토리로 만든다. v2 는 TryFrom 으로 만들고 같은 시계로 확인한다
+fn factory(clock: &ManualClock) -> RewardFactoryUnsafe<&ManualClock> {
+    RewardFactoryUnsafe::default().with_clock(clock)
+}
+
+proptest! {
+    // 같은 DTO 는 두 모델에서 같이 성공하거나 같은 이유로 실패한다
+    #[test]
+    fn construction_rules_match(dto in any_dto(), clock in clock()) {
+        let v1 = factory(&clock).try_gen(dto.clone()).map(|_| ());
+        let v2 = version_2::Reward::try_from(dto).map(|_| ());
+        prop_assert_eq!(v1, v2);
+    }
+
+    #[test]
+    fn display_hidden_and_expiry_match(dto in valid_dto(), clock in clock()) {
+        let factory = factory(&clock);
+        let v1 = factory.try_gen(dto.clone()).unwrap();
+        let v2 = version_2::Reward::try_from(dto).unwrap();
+
+        prop_assert_eq!(v1.is_hidden(), v2.is_hidden());
+        match v1.try_display() {
+            Some(display) => {
+                prop_assert!(!v2.is_hidden());
+                prop_assert_eq!(&*display.fmt_string, v2.fmt_string());
+                prop_assert_eq!(&*display.unit_image, v2.unit_image());
+                prop_assert_eq!(&*display.image, v2.image());
+            }
+            None => prop_assert!(v2.is_hidden()),
+        }
+
+        prop_assert_eq!(v1.shelf_life(), v2.get_shelf_life());
+        prop_assert_eq!(
+            v1.shelf_life().expired_at(factory.clock()).is_some(),
+            v2.has_expired(&clock)
+        );
+    }
+
+    #[test]
+    fn conversions_are_lossless(dto in valid_dto(), clock in clock()) {
+        let v1 = factory(&clock).try_gen(dto.clone()).unwrap();
+        let v2 = version_2::Reward::from(v1);
+        prop_assert_eq!(v2.to_dto(), Some(dto.clone()));
+
+        let v1 = RewardObj::from(v2);
+        prop_assert_eq!(v1.to_dto(), Some(dto));
+    }
+}
+
+#[test]
+fn unknown_converts_both_ways() {
+    let v1 = RewardObj::from(version_2::Reward::Unknown);
+    assert!(v1.to_dto().is_none());
+    assert!(matches!(
+        version_2::Reward::from(v1),
+        version_2::Reward::Unknown
+    ));
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "object")]
+
+use trait_practice::object::error::RewardError;
+use trait_practice::object::registry::RewardRegistry;
+use trait_practice::object::reward::{RewardDto, RewardGroup};
+use trait_practice::object::version_1::{Gem, RewardObj};
+
+// 어느 생성자가 불렸는지 delta 로 구분한다
+fn marker(delta: u32) -> impl Fn(RewardDto) -> Result<RewardObj, RewardError> + Send + Sync {
+    move |dto| {
+        Ok(Box::new(Gem {
+            currency: dto._type,
+            delta,
+            min: None,
+            max: None,
+            name: None,
+            shelf_life: None,
+            is_hidden: false,
+        }))
+    }
+}
+
+fn built(registry: &RewardRegistry, group: RewardGroup, _type: &str) -> Result<u32, RewardError> {
+    let dto = RewardDto {
+        _type: String::from(_type),
+        ..RewardDto::empty(group)
+    };
+    let reward = registry.build(dto)?;
+    Ok(reward.downcast_ref::<Gem>().unwrap().delta)
+}
+
+#[test]
+fn exact_match_wins_over_predicates() {
+    let mut registry = RewardRegistry::new();
+    registry
+        .register_if(|dto| dto.group == RewardGroup::ASSET, marker(1))
+        .register(RewardGroup::ASSET, "STAR", marker(2));
+    assert_eq!(built(&registry, RewardGroup::ASSET, "STAR"), Ok(2));
+    assert_eq!(built(&registry, RewardGroup::ASSET, "XP"), Ok(1));
+}
+
+#[test]
+fn predicates_are_tried_in_registration_order() {
+    let mut registry = RewardRegistry::new();
+    registry
+        .register_if(|dto| dto._type.starts_with("EVENT_"), marker(1))
+        .register_if(|dto| dto.group == RewardGroup::ASSET, marker(2));
+    assert_eq!(built(&registry, RewardGroup::ASSET, "EVENT_XP"), Ok(1));
+    assert_eq!(built(&registry, RewardGroup::ASSET, "XP"), Ok(2));
+}
+
+#[test]
+fn later_exact_registration_replaces_earlier() {
+    let mut registry = RewardRegistry::new();
+    registry
+        .register(RewardGroup::ASSET, "STAR", marker(1))
+        .register(RewardGroup::ASSET, "STAR", marker(2));
+    assert_eq!(built(&registry, RewardGroup::ASSET, "STAR"), Ok(2));
+}
+
+#[test]
+fn unmatched_dto_is_unknown_type() {
+    let mut registry = RewardRegistry::new();
+    registry.register(RewardGroup::ASSET, "STAR", marker(1));
+    assert_eq!(
+        built(&registry, RewardGroup::ASSET, "XP"),
+        Err(RewardError::UnknownType {
+            group: RewardGroup::ASSET,
+            _type: String::from("XP"),
+        })
+    );
+    assert_eq!(
+        built(&RewardRegistry::new(), RewardGroup::QUEST, "DAILY"),
+        Err(RewardError::UnknownType {
+            group: RewardGroup::QUEST,
+            _type: String::from("DAILY"),
+        })
+    );
+}
+
+#[test]
+fn unknown_group_keeps_its_name() {
+    let group = RewardGroup::Unknown(String::from("BADGE"));
+    assert_eq!(
+        built(&RewardRegistry::builtin(), group.clone(), "GOLD"),
+        Err(RewardError::UnknownGroup(String::from("BADGE")))
+    );
+
+    // 등록만 되어 있으면 모르는 그룹도 만들 수 있다
+    let mut registry = RewardRegistry::builtin();
+    registry.register(group.clone(), "GOLD", marker(7));
+    assert_eq!(built(&registry, group, "GOLD"), Ok(7));
+}
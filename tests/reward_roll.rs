@@ -0,0 +1,143 @@
+#![cfg(feature = "object")]
+
+use proptest::prelude::*;
+use trait_practice::object::inventory::{grant, GrantError, Inventory};
+use trait_practice::object::registry::RewardRegistry;
+use trait_practice::object::reward::{RewardDto, RewardGroup};
+use trait_practice::object::rng::{RewardRng, SeededRng};
+use trait_practice::object::version_1::{Gem, Reward, RewardObj};
+use trait_practice::object::version_2;
+
+fn ranged(min: u32, max: u32) -> Gem {
+    Gem {
+        currency: String::from("STAR"),
+        delta: 0,
+        min: Some(min),
+        max: Some(max),
+        name: None,
+        shelf_life: None,
+        is_hidden: false,
+    }
+}
+
+fn ranged_v2(min: u32, max: u32) -> version_2::Reward {
+    version_2::Reward::Gem {
+        currency: String::from("STAR"),
+        delta: 0,
+        min: Some(min),
+        max: Some(max),
+        name: None,
+        shelf_life: None,
+        is_hidden: false,
+    }
+}
+
+#[test]
+fn same_seed_gives_same_roll() {
+    let rolls = |seed| {
+        let mut rng = SeededRng::new(seed);
+        (0..8)
+            .map(|_| {
+                let mut gem = ranged(1, 1000);
+                gem.roll(&mut rng);
+                gem.delta
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(rolls(42), rolls(42));
+    assert_ne!(rolls(42), rolls(43));
+
+    // v2 도 같은 시드, 같은 범위면 같은 값을 굴린다
+    let mut v1 = ranged(1, 1000);
+    v1.roll(&mut SeededRng::new(7));
+    let mut v2 = ranged_v2(1, 1000);
+    v2.roll(&mut SeededRng::new(7));
+    assert!(matches!(v2, version_2::Reward::Gem { delta, .. } if delta == v1.delta));
+}
+
+#[test]
+fn unrolled_gem_is_not_granted() {
+    let mut inventory = Inventory::new();
+    let unrolled: RewardObj = Box::new(ranged(10, 20));
+    assert_eq!(
+        grant(&mut inventory, &unrolled),
+        Err(GrantError::Unrolled {
+            currency: String::from("STAR")
+        })
+    );
+    assert_eq!(inventory.balance("STAR"), 0);
+
+    let mut rolled = unrolled;
+    rolled.roll(&mut SeededRng::new(1));
+    grant(&mut inventory, &rolled).unwrap();
+    assert!((10..=20).contains(&inventory.balance("STAR")));
+}
+
+#[test]
+fn fixed_gem_is_not_rerolled() {
+    let mut gem = Gem {
+        delta: 300,
+        min: None,
+        max: None,
+        ..ranged(0, 0)
+    };
+    gem.roll(&mut SeededRng::new(1));
+    assert_eq!(gem.delta, 300);
+}
+
+#[test]
+fn roll_leaves_rewards_without_range_unchanged() {
+    let dtos = [
+        RewardDto {
+            _type: String::from("POUCH"),
+            name: Some(String::from("Gem Pouch")),
+            url: Some(String::from("https://static.playio.club/pouch.png")),
+            delta: 2,
+            ..RewardDto::empty(RewardGroup::ITEM)
+        },
+        RewardDto {
+            _type: String::from("FRAME_GOLD"),
+            url: Some(String::from("https://static.playio.club/frame_gold.png")),
+            delta: 1,
+            ..RewardDto::empty(RewardGroup::AVATAR)
+        },
+        RewardDto {
+            _type: String::from("DAILY_LOGIN"),
+            name: Some(String::from("Daily Login")),
+            delta: 7,
+            ..RewardDto::empty(RewardGroup::QUEST)
+        },
+    ];
+    for dto in dtos {
+        let mut reward = RewardRegistry::builtin().build(dto.clone()).unwrap();
+        reward.roll(&mut SeededRng::new(1));
+        assert_eq!(reward.to_dto(), Some(dto));
+    }
+}
+
+proptest! {
+    #[test]
+    fn rolled_delta_stays_in_range(seed in any::<u64>(), a in any::<u32>(), b in any::<u32>()) {
+        let (min, max) = (a.min(b), a.max(b));
+        let mut gem = ranged(min, max);
+        gem.roll(&mut SeededRng::new(seed));
+        prop_assert!((min..=max).contains(&gem.delta));
+        prop_assert_eq!((gem.min, gem.max), (None, None));
+
+        let mut v2 = ranged_v2(min, max);
+        v2.roll(&mut SeededRng::new(seed));
+        let version_2::Reward::Gem { delta, min: lo, max: hi, .. } = v2 else {
+            unreachable!()
+        };
+        prop_assert_eq!(delta, gem.delta);
+        prop_assert_eq!((lo, hi), (None, None));
+    }
+
+    #[test]
+    fn range_inclusive_hits_both_ends(seed in any::<u64>()) {
+        let mut rng = SeededRng::new(seed);
+        let rolls: Vec<u32> = (0..200).map(|_| rng.range_inclusive(3, 5)).collect();
+        prop_assert!(rolls.iter().all(|n| (3..=5).contains(n)));
+        prop_assert!(rolls.contains(&3) && rolls.contains(&5));
+    }
+}
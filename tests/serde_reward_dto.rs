@@ -0,0 +1,80 @@
+#![cfg(all(feature = "object", feature = "serde"))]
+
+use chrono::{TimeZone, Utc};
+use trait_practice::object::reward::{RewardDto, RewardGroup};
+
+const ASSET_PAYLOAD: &str = r#"{
+    "group": "ASSET",
+    "type": "XP",
+    "delta": 3000,
+    "min": 100,
+    "max": 500,
+    "isHidden": false
+}"#;
+
+const ITEM_PAYLOAD: &str = r#"{
+    "group": "ITEM",
+    "type": "1000XP",
+    "name": "1,000-Gem Pouch",
+    "delta": 1,
+    "url": "https://static.playio.club/__asset/item_1000_gem_pack.png",
+    "isHidden": true,
+    "shelfLife": "2023-07-01T09:30:00Z"
+}"#;
+
+const UNKNOWN_GROUP_PAYLOAD: &str = r#"{
+    "group": "SEASON_PASS",
+    "type": "PREMIUM",
+    "delta": 1
+}"#;
+
+#[test]
+fn deserializes_asset_payload() {
+    let dto: RewardDto = serde_json::from_str(ASSET_PAYLOAD).unwrap();
+    assert_eq!(dto.group, RewardGroup::ASSET);
+    assert_eq!(dto._type, "XP");
+    assert_eq!(dto.delta, 3000);
+    assert_eq!((dto.min, dto.max), (Some(100), Some(500)));
+    assert_eq!(dto.name, None);
+    assert_eq!(dto.shelf_life, None);
+}
+
+#[test]
+fn deserializes_item_payload_with_shelf_life() {
+    let dto: RewardDto = serde_json::from_str(ITEM_PAYLOAD).unwrap();
+    assert_eq!(dto.group, RewardGroup::ITEM);
+    assert_eq!(dto.name.as_deref(), Some("1,000-Gem Pouch"));
+    assert!(dto.is_hidden);
+    assert_eq!(
+        dto.shelf_life,
+        Some(Utc.with_ymd_and_hms(2023, 7, 1, 9, 30, 0).unwrap())
+    );
+}
+
+#[test]
+fn keeps_unknown_group() {
+    let dto: RewardDto = serde_json::from_str(UNKNOWN_GROUP_PAYLOAD).unwrap();
+    assert_eq!(dto.group, RewardGroup::Unknown(String::from("SEASON_PASS")));
+    assert!(!dto.is_hidden);
+
+    let json = serde_json::to_value(&dto).unwrap();
+    assert_eq!(json["group"], "SEASON_PASS");
+}
+
+#[test]
+fn round_trips_sample_payloads() {
+    for payload in [ASSET_PAYLOAD, ITEM_PAYLOAD, UNKNOWN_GROUP_PAYLOAD] {
+        let dto: RewardDto = serde_json::from_str(payload).unwrap();
+        let json = serde_json::to_string(&dto).unwrap();
+        let back: RewardDto = serde_json::from_str(&json).unwrap();
+        assert_eq!(dto, back);
+
+        let original: serde_json::Value = serde_json::from_str(payload).unwrap();
+        let mut serialized = serde_json::to_value(&dto).unwrap();
+        // isHidden 은 기본값(false)이라도 항상 내보낸다
+        if original.get("isHidden").is_none() {
+            serialized.as_object_mut().unwrap().remove("isHidden");
+        }
+        assert_eq!(original, serialized);
+    }
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "object")]
+
+mod common;
+
+use chrono::Duration;
+use common::start;
+use trait_practice::object::clock::ManualClock;
+use trait_practice::object::reward::{RewardDto, RewardGroup};
+use trait_practice::object::version_1::{
+    Coupon, Item, RewardFactory, RewardFactorySafe, RewardFactoryUnsafe, ShelfLife,
+};
+use trait_practice::object::version_2::{self, TimeLimited};
+
+// start 로부터 하루 뒤에 만료되는 아이템
+fn item() -> RewardDto {
+    RewardDto {
+        _type: String::from("1000XP"),
+        name: Some(String::from("1,000-Gem Pouch")),
+        url: Some(String::from("https://static.playio.club/pouch.png")),
+        delta: 1,
+        shelf_life: Some(start() + Duration::days(1)),
+        ..RewardDto::empty(RewardGroup::ITEM)
+    }
+}
+
+fn coupon() -> RewardDto {
+    RewardDto {
+        _type: String::from("WELCOME"),
+        code: Some(String::from("PLAYIO-2023")),
+        shelf_life: Some(start() + Duration::days(1)),
+        ..RewardDto::empty(RewardGroup::COUPON)
+    }
+}
+
+#[test]
+fn v1_expired_at_flips_when_clock_passes_shelf_life() {
+    let clock = ManualClock::new(start());
+    let item = Item::try_from(item()).unwrap();
+    let coupon = Coupon::try_from(coupon()).unwrap();
+    let at = start() + Duration::days(1);
+
+    assert_eq!(item.expired_at(&clock), None);
+    assert_eq!(coupon.expired_at(&clock), None);
+
+    // 유통기한 그 시각까지는 유효하다
+    clock.set(at);
+    assert_eq!(item.expired_at(&clock), None);
+
+    clock.advance(Duration::seconds(1));
+    assert_eq!(item.expired_at(&clock), Some(&at));
+    assert_eq!(coupon.expired_at(&clock), Some(&at));
+    assert_eq!(item.shelf_life.expired_at(&clock), Some(&at));
+
+    // 시계를 되돌리면 다시 유효하다
+    clock.set(start());
+    assert_eq!(item.expired_at(&clock), None);
+}
+
+#[test]
+fn v2_has_expired_flips_when_clock_passes_shelf_life() {
+    let clock = ManualClock::new(start());
+    for dto in [item(), coupon()] {
+        let reward = version_2::Reward::try_from(dto).unwrap();
+        assert!(!reward.has_expired(&clock));
+        assert_eq!(reward.fmt_expire_time(), "2023-07-02 00:00:00");
+    }
+
+    clock.advance(Duration::days(1) + Duration::seconds(1));
+    for dto in [item(), coupon()] {
+        let reward = version_2::Reward::try_from(dto).unwrap();
+        assert!(reward.has_expired(&clock));
+    }
+
+    // 유통기한이 없는 보상은 만료되지 않는다
+    let gem = version_2::Reward::try_from(RewardDto::empty(RewardGroup::ASSET))
+        .ok()
+        .unwrap();
+    clock.advance(Duration::days(365 * 100));
+    assert!(!gem.has_expired(&clock));
+    assert_eq!(gem.fmt_expire_time(), "");
+}
+
+#[test]
+fn factories_build_expired_rewards_and_share_their_clock() {
+    let clock = ManualClock::new(start() + Duration::days(2));
+    let at = start() + Duration::days(1);
+
+    // 만료 여부는 생성 규칙이 아니라 조회다. 이미 지난 보상도 만들어진다
+    let unsafe_factory = RewardFactoryUnsafe::default().with_clock(&clock);
+    let reward = unsafe_factory.try_gen(item()).unwrap();
+    let item = reward.downcast_ref::<Item>().unwrap();
+    assert_eq!(item.expired_at(unsafe_factory.clock()), Some(&at));
+
+    let safe_factory =
+        RewardFactorySafe::new(|_: &_| panic!("nothing to report")).with_clock(&clock);
+    let reward = safe_factory.gen(coupon());
+    let coupon = reward.downcast_ref::<Coupon>().unwrap();
+    assert_eq!(coupon.expired_at(safe_factory.clock()), Some(&at));
+
+    clock.set(start());
+    assert_eq!(safe_factory.clock().now(), start());
+    assert_eq!(coupon.expired_at(safe_factory.clock()), None);
+}
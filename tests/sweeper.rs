@@ -0,0 +1,167 @@
+#![cfg(feature = "object")]
+
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use common::start;
+use trait_practice::object::clock::ManualClock;
+use trait_practice::object::registry::RewardRegistry;
+use trait_practice::object::reward::{RewardDto, RewardGroup};
+use trait_practice::object::sweeper::{Expirable, Sweeper};
+use trait_practice::object::version_1::{RewardBundle, RewardObj};
+use trait_practice::object::version_2;
+
+fn item(name: &str, shelf_life: Option<DateTime<Utc>>) -> RewardDto {
+    RewardDto {
+        _type: String::from("POUCH"),
+        name: Some(String::from(name)),
+        url: Some(String::from("https://static.playio.club/pouch.png")),
+        delta: 1,
+        shelf_life,
+        ..RewardDto::empty(RewardGroup::ITEM)
+    }
+}
+
+// 1시간 뒤, 3시간 뒤 만료되는 아이템과 만료되지 않는 재화
+fn dtos() -> Vec<RewardDto> {
+    vec![
+        item("A", Some(start() + Duration::hours(1))),
+        item("B", Some(start() + Duration::hours(3))),
+        RewardDto::empty(RewardGroup::ASSET),
+    ]
+}
+
+fn v1() -> Vec<RewardObj> {
+    let registry = RewardRegistry::builtin();
+    dtos()
+        .into_iter()
+        .map(|dto| registry.build(dto).unwrap())
+        .collect()
+}
+
+fn v2() -> Vec<version_2::Reward> {
+    let rewards = dtos().into_iter().map(version_2::Reward::try_from);
+    rewards.map(|reward| reward.unwrap()).collect()
+}
+
+// [살아 있는 보상, 치운 보상]
+fn split<T: Expirable>(sweeper: &Sweeper, rewards: Vec<T>) -> [Vec<String>; 2] {
+    let sweep = sweeper.sweep(rewards);
+    let live = sweep.live.iter().map(Expirable::describe).collect();
+    let expired = sweep.expired.iter().map(|e| e.reward.describe()).collect();
+    [live, expired]
+}
+
+#[test]
+fn sweep_splits_live_and_expired() {
+    let clock = ManualClock::new(start());
+    let sweeper = Sweeper::new(&clock);
+    let expected = [vec!["A Item", "B Item", "0 Gems"], vec![]];
+    assert_eq!(split(&sweeper, v1()), expected);
+    assert_eq!(split(&sweeper, v2()), expected);
+
+    clock.advance(Duration::hours(2));
+    let expected = [vec!["B Item", "0 Gems"], vec!["A Item"]];
+    assert_eq!(split(&sweeper, v1()), expected);
+    assert_eq!(split(&sweeper, v2()), expected);
+
+    let sweep = sweeper.sweep(v2());
+    assert_eq!(sweep.expired[0].at, start() + Duration::hours(1));
+    assert_eq!(sweep.report(), ["A Item (EXPIRED AT 2023-07-01 01:00:00)"]);
+
+    clock.advance(Duration::hours(2));
+    let expected = [vec!["0 Gems"], vec!["A Item", "B Item"]];
+    assert_eq!(split(&sweeper, v1()), expected);
+}
+
+#[test]
+fn grace_period_delays_sweep() {
+    let clock = ManualClock::new(start() + Duration::hours(1) + Duration::minutes(20));
+    let sweeper = Sweeper::new(&clock).with_grace(Duration::minutes(30));
+    assert!(sweeper.sweep(v1()).expired.is_empty());
+
+    // 유통기한 + grace 그 시각까지는 남겨 둔다
+    clock.set(start() + Duration::hours(1) + Duration::minutes(30));
+    assert!(sweeper.sweep(v2()).expired.is_empty());
+
+    clock.advance(Duration::seconds(1));
+    let [_, expired] = split(&sweeper, v1());
+    assert_eq!(expired, ["A Item"]);
+}
+
+#[test]
+fn next_expiry_is_the_nearest_future_deadline() {
+    let clock = ManualClock::new(start());
+    let sweeper = Sweeper::new(&clock);
+    let (v1, v2) = (v1(), v2());
+    assert_eq!(sweeper.next_expiry(&v1), Some(start() + Duration::hours(1)));
+    assert_eq!(sweeper.next_expiry(&v2), Some(start() + Duration::hours(1)));
+
+    let graced = Sweeper::new(&clock).with_grace(Duration::minutes(30));
+    let deadline = start() + Duration::hours(1) + Duration::minutes(30);
+    assert_eq!(graced.next_expiry(&v1), Some(deadline));
+
+    clock.advance(Duration::hours(2));
+    assert_eq!(sweeper.next_expiry(&v2), Some(start() + Duration::hours(3)));
+    clock.advance(Duration::hours(2));
+    assert_eq!(sweeper.next_expiry(&v1), None);
+}
+
+#[test]
+fn deadline_past_the_end_of_time_is_never_swept() {
+    let clock = ManualClock::new(DateTime::<Utc>::MAX_UTC);
+    let sweeper = Sweeper::new(&clock).with_grace(Duration::days(1));
+    let forever = item("Forever", Some(DateTime::<Utc>::MAX_UTC));
+    let rewards = vec![version_2::Reward::try_from(forever).unwrap()];
+    assert_eq!(sweeper.next_expiry(&rewards), None);
+    let sweep = sweeper.sweep(rewards);
+    assert_eq!(sweep.live.len(), 1);
+    assert!(sweep.expired.is_empty());
+}
+
+#[test]
+fn bundle_expires_with_its_earliest_reward() {
+    let clock = ManualClock::new(start() + Duration::hours(2));
+    let sweeper = Sweeper::new(&clock);
+    let chest: Vec<RewardObj> = vec![Box::new(RewardBundle::new("Chest", v1()))];
+    assert_eq!(chest[0].shelf_life(), Some(start() + Duration::hours(1)));
+    let v2 = vec![version_2::Reward::bundle("Chest", v2())];
+    assert_eq!(v2[0].shelf_life(), Some(start() + Duration::hours(1)));
+
+    let [live, expired] = split(&sweeper, chest);
+    assert!(live.is_empty());
+    assert_eq!(expired, ["Chest (A Item + B Item + 0 Gems)"]);
+    assert_eq!(split(&sweeper, v2)[1], expired);
+
+    // 만료되는 보상이 없는 묶음은 남는다
+    let gems = vec![version_2::Reward::bundle("Gems", vec![])];
+    assert_eq!(sweeper.next_expiry(&gems), None);
+    assert_eq!(sweeper.sweep(gems).live.len(), 1);
+}
+
+#[test]
+fn nested_bundle_expires_with_its_earliest_inner_reward() {
+    let inner = RewardBundle::new("Inner", v1());
+    let outer: RewardObj = Box::new(RewardBundle::new("Outer", vec![Box::new(inner)]));
+    assert_eq!(outer.shelf_life(), Some(start() + Duration::hours(1)));
+
+    let inner = version_2::Reward::bundle("Inner", v2());
+    let outer = version_2::Reward::bundle("Outer", vec![inner]);
+    assert_eq!(outer.shelf_life(), Some(start() + Duration::hours(1)));
+}
+
+#[test]
+fn hidden_rewards_are_described_the_same_in_v1_and_v2() {
+    let clock = ManualClock::new(start() + Duration::hours(2));
+    let sweeper = Sweeper::new(&clock);
+    let secret = RewardDto {
+        is_hidden: true,
+        ..item("Secret", Some(start() + Duration::hours(1)))
+    };
+    let v1 = vec![RewardRegistry::builtin().build(secret.clone()).unwrap()];
+    let v2 = vec![version_2::Reward::try_from(secret).unwrap()];
+
+    let expected = [vec![], vec!["(hidden reward)"]];
+    assert_eq!(split(&sweeper, v1), expected);
+    assert_eq!(split(&sweeper, v2), expected);
+}